@@ -2,6 +2,11 @@ use std::str::FromStr;
 
 use anchor_lang::solana_program::pubkey::Pubkey;
 
-pub const NATIVE_MINT_STR: &'static str = "So11111111111111111111111111111111111111112"; //TODO:
+pub const NATIVE_MINT_STR: &str = "So11111111111111111111111111111111111111112"; //TODO:
 
 pub const FEE_PER_DIV: u128 = 1000;
+
+pub const BPS_DIV: u128 = 10_000;
+
+// total time a creator may keep a pod paused, in seconds
+pub const MAX_PAUSE_DURATION: i64 = 3 * 24 * 60 * 60;
@@ -28,4 +28,127 @@ pub enum MemepodError {
     
     #[msg("Token symbol too long")]
     TokenSymbolTooLong,
+
+    #[msg("Mint authority not revoked")]
+    MintAuthorityNotRevoked,
+
+    #[msg("Freeze authority not revoked")]
+    FreezeAuthorityNotRevoked,
+
+    #[msg("Uri too long")]
+    UriTooLong,
+
+    #[msg("Slippage exceeded")]
+    SlippageExceeded,
+
+    #[msg("Price limit exceeded")]
+    PriceLimitExceeded,
+
+    #[msg("Invalid curve parameters")]
+    InvalidCurve,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Pod expired")]
+    PodExpired,
+
+    #[msg("Pod not expired yet")]
+    PodNotExpired,
+
+    #[msg("Invalid expire time")]
+    InvalidExpireTime,
+
+    #[msg("Invalid cap")]
+    InvalidCap,
+
+    #[msg("Hard cap reached")]
+    HardCapReached,
+
+    #[msg("Soft cap not reached")]
+    SoftCapNotReached,
+
+    #[msg("Not refundable")]
+    NotRefundable,
+
+    #[msg("Pod not finished")]
+    PodNotFinished,
+
+    #[msg("Invalid liquidity share")]
+    InvalidLiquidityShare,
+
+    #[msg("Graduation disabled")]
+    GraduationDisabled,
+
+    #[msg("Target not reached")]
+    TargetNotReached,
+
+    #[msg("Invalid lock duration")]
+    InvalidLockDuration,
+
+    #[msg("Liquidity still locked")]
+    LiquidityLocked,
+
+    #[msg("Proceeds escrowed")]
+    ProceedsEscrowed,
+
+    #[msg("Pod already started")]
+    PodAlreadyStarted,
+
+    #[msg("Invalid milestones")]
+    InvalidMilestones,
+
+    #[msg("Milestone closed")]
+    MilestoneClosed,
+
+    #[msg("Milestone vote still open")]
+    MilestoneVoteOpen,
+
+    #[msg("Already voted")]
+    AlreadyVoted,
+
+    #[msg("Milestones not failed")]
+    MilestoneNotFailed,
+
+    #[msg("Already reclaimed")]
+    AlreadyReclaimed,
+
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Invalid status transition")]
+    InvalidStatusTransition,
+
+    #[msg("Pod not started")]
+    PodNotStarted,
+
+    #[msg("Invalid start time")]
+    InvalidStartTime,
+
+    #[msg("Pod paused")]
+    PodPaused,
+
+    #[msg("Pause limit reached")]
+    PauseLimitReached,
+
+    #[msg("Protocol paused")]
+    ProtocolPaused,
+
+    #[msg("Invalid registry page")]
+    InvalidRegistryPage,
+
+    #[msg("Missing quote account")]
+    MissingQuoteAccount,
+
+    #[msg("Price locked after the first buy")]
+    PriceLocked,
+
+    #[msg("Pod has buyers")]
+    PodHasBuyers,
+
+    #[msg("Pod must graduate")]
+    PodMustGraduate,
 }
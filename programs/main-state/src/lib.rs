@@ -4,6 +4,7 @@ use anchor_lang::prelude::*;
 
 pub mod main_state;
 pub mod pod;
+pub mod pool;
 
 pub mod constants;
 pub mod error;
@@ -11,6 +12,7 @@ pub mod utils;
 
 use main_state::*;
 use pod::*;
+use pool::*;
 
 declare_id!("5EFN2ja837Uk3setSnu99JvSfx8H8sNWKx3Hndm3XeKb");
 
@@ -25,24 +27,155 @@ pub mod memepod {
     pub fn update_main_state(ctx: Context<AUpdateMainState>, input: UpdateMainStateInput) -> Result<()> {
         main_state::update_main_state(ctx, input)
     }
+
+    pub fn set_pause_flags(ctx: Context<ASetPauseFlags>, paused_flags: u16) -> Result<()> {
+        main_state::set_pause_flags(ctx, paused_flags)
+    }
+
+    pub fn set_quote_mint(ctx: Context<ASetQuoteMint>, input: QuoteMintInput) -> Result<()> {
+        main_state::set_quote_mint(ctx, input)
+    }
+
+    pub fn update_pauser(ctx: Context<AUpdatePauser>, pauser: Pubkey) -> Result<()> {
+        main_state::update_pauser(ctx, pauser)
+    }
+
+    pub fn migrate_main_state(ctx: Context<AMigrateMainState>) -> Result<()> {
+        main_state::migrate_main_state(ctx)
+    }
     
     pub fn create_pod(ctx: Context<ACreatePod>, input: CreatePodInput) -> Result<()> {
         pod::create_pod(ctx, input)
     }
 
-    pub fn buy(ctx: Context<ABuy>, amount: u64) -> Result<()> {
-        pod::buy(ctx, amount)
+    pub fn create_pod_with_mint(
+        ctx: Context<ACreatePodWithMint>,
+        input: CreatePodInput,
+        mint_input: CreateMintInput,
+    ) -> Result<()> {
+        pod::create_pod_with_mint(ctx, input, mint_input)
+    }
+
+    pub fn buy(
+        ctx: Context<ABuy>,
+        amount: u64,
+        min_base_out: u64,
+        max_price: Option<u64>,
+    ) -> Result<()> {
+        pod::buy(ctx, amount, min_base_out, max_price)
+    }
+
+    pub fn buy_exact_out(
+        ctx: Context<ABuy>,
+        base_amount: u64,
+        max_quote_in: u64,
+    ) -> Result<()> {
+        pod::buy_exact_out(ctx, base_amount, max_quote_in)
+    }
+
+    pub fn sell(ctx: Context<ASell>, base_amount: u64, min_quote_out: u64) -> Result<()> {
+        pod::sell(ctx, base_amount, min_quote_out)
     }
     
     pub fn withdraw(ctx: Context<AWithdrawState>, input: WithdrawInput) -> Result<()> {
         pod::withdraw(ctx, input)
     }
 
+    pub fn refund(ctx: Context<ARefund>, base_amount: u64) -> Result<()> {
+        pod::refund(ctx, base_amount)
+    }
+
+    pub fn finalize_pod(ctx: Context<AFinalizePod>) -> Result<()> {
+        pod::finalize_pod(ctx)
+    }
+
+    pub fn claim(ctx: Context<AClaim>, base_amount: u64) -> Result<()> {
+        pod::claim(ctx, base_amount)
+    }
+
+    pub fn claim_creator_vesting(
+        ctx: Context<AClaimCreatorVesting>,
+        base_amount: u64,
+    ) -> Result<()> {
+        pod::claim_creator_vesting(ctx, base_amount)
+    }
+
+    pub fn close_position(ctx: Context<AClosePosition>) -> Result<()> {
+        pod::close_position(ctx)
+    }
+
     pub fn close_pod(ctx: Context<AClosePodState>) -> Result<()> {
         pod::close_pod(ctx)
     }
 
+    pub fn withdraw_legacy_pod(ctx: Context<AWithdrawLegacyPod>) -> Result<()> {
+        pod::withdraw_legacy_pod(ctx)
+    }
+
     pub fn edit_pod(ctx: Context<AEditPodState>, input: EditPodInput) -> Result<()> {
         pod::edit_pod(ctx, input)
     }
+
+    pub fn reschedule_pod(
+        ctx: Context<AReschedulePod>,
+        start_time: u64,
+        expire_time: Option<u64>,
+    ) -> Result<()> {
+        pod::reschedule_pod(ctx, start_time, expire_time)
+    }
+
+    pub fn pause_pod(ctx: Context<APausePod>) -> Result<()> {
+        pod::pause_pod(ctx)
+    }
+
+    pub fn resume_pod(ctx: Context<APausePod>) -> Result<()> {
+        pod::resume_pod(ctx)
+    }
+
+    pub fn vote_milestone(ctx: Context<AVoteMilestone>, index: u8, approve: bool) -> Result<()> {
+        pod::vote_milestone(ctx, index, approve)
+    }
+
+    pub fn resolve_milestone(ctx: Context<AResolveMilestone>, index: u8) -> Result<()> {
+        pod::resolve_milestone(ctx, index)
+    }
+
+    pub fn reclaim_milestone_funds(ctx: Context<AReclaimMilestoneFunds>) -> Result<()> {
+        pod::reclaim_milestone_funds(ctx)
+    }
+
+    pub fn graduate_pod(ctx: Context<AGraduatePod>) -> Result<()> {
+        pool::graduate_pod(ctx)
+    }
+
+    pub fn swap(
+        ctx: Context<ASwap>,
+        amount_in: u64,
+        min_amount_out: u64,
+        base_to_quote: bool,
+    ) -> Result<()> {
+        pool::swap(ctx, amount_in, min_amount_out, base_to_quote)
+    }
+
+    pub fn add_liquidity(
+        ctx: Context<AAddLiquidity>,
+        lp_amount: u64,
+        max_base_in: u64,
+        max_quote_in: u64,
+    ) -> Result<()> {
+        pool::add_liquidity(ctx, lp_amount, max_base_in, max_quote_in)
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<ARemoveLiquidity>,
+        lp_amount: u64,
+        min_base_out: u64,
+        min_quote_out: u64,
+    ) -> Result<()> {
+        pool::remove_liquidity(ctx, lp_amount, min_base_out, min_quote_out)
+    }
+
+    pub fn unlock_liquidity(ctx: Context<AUnlockLiquidity>) -> Result<()> {
+        pool::unlock_liquidity(ctx)
+    }
 }
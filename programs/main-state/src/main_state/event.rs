@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PauseFlagsEvent {
+    pub authority: Pubkey,
+    pub previous_flags: u16,
+    pub paused_flags: u16,
+    pub timestamp: i64,
+}
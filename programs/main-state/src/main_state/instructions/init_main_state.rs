@@ -11,6 +11,7 @@ pub fn init_main_state(ctx: Context<AInitMainState>) -> Result<()> {
     state.initialized = true;
     state.owner = ctx.accounts.owner.key();
     state.fee_recipient = ctx.accounts.owner.key();
+    state.pauser = ctx.accounts.owner.key();
     state.trading_fee = 1_000;   // default: 1%
     state.creator_fee = 1_000;
     state.owner_fee = 1_000;
@@ -0,0 +1,58 @@
+use crate::{error::MemepodError, utils::transfer_lamports, MainState};
+use anchor_lang::prelude::*;
+
+// offset of `MainState::owner`: discriminator, then the `initialized` flag
+const OWNER_OFFSET: usize = 8 + 1;
+
+// grows a MainState created before `pauser`/`paused_flags` existed, the new fields start zeroed
+pub fn migrate_main_state(ctx: Context<AMigrateMainState>) -> Result<()> {
+    let main_state = ctx.accounts.main_state.to_account_info();
+    let space = 8 + MainState::MAX_SIZE;
+    if main_state.data_len() >= space {
+        return Ok(());
+    }
+
+    // the old layout can't be deserialized as MainState, so the owner is read directly
+    let owner = {
+        let data = main_state.try_borrow_data()?;
+        require!(
+            data.len() >= OWNER_OFFSET + 32,
+            MemepodError::Uninitialized
+        );
+        Pubkey::try_from(&data[OWNER_OFFSET..OWNER_OFFSET + 32]).unwrap()
+    };
+    require!(
+        owner == ctx.accounts.owner.key(),
+        MemepodError::Unauthorised
+    );
+
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(main_state.lamports());
+    transfer_lamports(
+        ctx.accounts.owner.to_account_info(),
+        main_state.clone(),
+        rent,
+        ctx.accounts.system_program.to_account_info(),
+        &[],
+    )?;
+    main_state.realloc(space, true)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AMigrateMainState<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: still in the pre-pause layout, the owner is checked in the handler
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub main_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
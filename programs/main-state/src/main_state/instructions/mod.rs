@@ -3,3 +3,12 @@ pub use init_main_state::*;
 
 pub mod update_main_state_owner;
 pub use update_main_state_owner::*;
+
+pub mod set_pause_flags;
+pub use set_pause_flags::*;
+
+pub mod set_quote_mint;
+pub use set_quote_mint::*;
+
+pub mod migrate_main_state;
+pub use migrate_main_state::*;
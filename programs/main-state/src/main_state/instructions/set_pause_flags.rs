@@ -0,0 +1,58 @@
+use crate::{error::MemepodError, MainState, PauseFlagsEvent};
+use anchor_lang::prelude::*;
+
+pub fn set_pause_flags(ctx: Context<ASetPauseFlags>, paused_flags: u16) -> Result<()> {
+    let state = &mut ctx.accounts.main_state;
+    require!(state.initialized.eq(&true), MemepodError::Uninitialized);
+
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == state.pauser || authority == state.owner,
+        MemepodError::Unauthorised
+    );
+
+    let previous_flags = state.paused_flags;
+    state.paused_flags = paused_flags;
+
+    emit!(PauseFlagsEvent {
+        authority,
+        previous_flags,
+        paused_flags,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn update_pauser(ctx: Context<AUpdatePauser>, pauser: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.main_state;
+    require!(state.initialized.eq(&true), MemepodError::Uninitialized);
+
+    state.pauser = pauser;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetPauseFlags<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
+
+#[derive(Accounts)]
+pub struct AUpdatePauser<'info> {
+    #[account(mut, address = main_state.owner @ MemepodError::Unauthorised)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        has_one = owner,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
@@ -0,0 +1,56 @@
+use crate::{error::MemepodError, utils::is_native_mint, MainState, QuoteMintConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy)]
+pub struct QuoteMintInput {
+    enabled: bool,
+    creation_fee: u64,
+    trading_fee: u16,
+    creator_fee: u16,
+    owner_fee: u16,
+}
+
+pub fn set_quote_mint(ctx: Context<ASetQuoteMint>, input: QuoteMintInput) -> Result<()> {
+    let state = &ctx.accounts.main_state;
+    require!(state.initialized.eq(&true), MemepodError::Uninitialized);
+    // wrapped SOL fees live on MainState
+    require!(
+        !is_native_mint(&ctx.accounts.quote_mint.key()),
+        MemepodError::UnknownToken
+    );
+
+    let config = &mut ctx.accounts.quote_config;
+    config.mint = ctx.accounts.quote_mint.key();
+    config.enabled = input.enabled;
+    config.creation_fee = input.creation_fee;
+    config.trading_fee = input.trading_fee;
+    config.creator_fee = input.creator_fee;
+    config.owner_fee = input.owner_fee;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetQuoteMint<'info> {
+    #[account(mut, address = main_state.owner @ MemepodError::Unauthorised)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+        has_one = owner,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    pub quote_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [QuoteMintConfig::PREFIX_SEED, quote_mint.key().as_ref()],
+        bump,
+        space = 8 + QuoteMintConfig::MAX_SIZE
+    )]
+    pub quote_config: Account<'info, QuoteMintConfig>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -3,3 +3,6 @@ pub use instructions::*;
 
 pub mod state;
 pub use state::*;
+
+pub mod event;
+pub use event::*;
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::MemepodError, utils::is_native_mint};
+
 #[account]
 pub struct MainState {
     pub initialized: bool,
@@ -8,10 +10,204 @@ pub struct MainState {
     pub creation_fee: u64,
     pub trading_fee: u16,
     pub creator_fee: u16,
-    pub owner_fee: u16
+    pub owner_fee: u16,
+    pub pauser: Pubkey,
+    pub paused_flags: u16,
 }
 
 impl MainState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
     pub const PREFIX_SEED: &'static [u8] = b"main";
+
+    // paused_flags bits, one per group of pod and pool instructions
+    pub const PAUSE_CREATE: u16 = 1 << 0;
+    pub const PAUSE_BUY: u16 = 1 << 1;
+    pub const PAUSE_SELL: u16 = 1 << 2;
+    pub const PAUSE_WITHDRAW: u16 = 1 << 3;
+    pub const PAUSE_REFUND: u16 = 1 << 4;
+    pub const PAUSE_CLAIM: u16 = 1 << 5;
+    pub const PAUSE_FINALIZE: u16 = 1 << 6;
+    pub const PAUSE_MANAGE: u16 = 1 << 7;
+    pub const PAUSE_MILESTONES: u16 = 1 << 8;
+    pub const PAUSE_POOL: u16 = 1 << 9;
+    pub const PAUSE_ALL: u16 = u16::MAX;
+
+    pub fn require_not_paused(&self, flag: u16) -> Result<()> {
+        require!(self.paused_flags & flag == 0, MemepodError::ProtocolPaused);
+        Ok(())
+    }
+
+    // fees for pods quoted in `quote_mint`, wrapped SOL falls back to the MainState fees
+    pub fn fee_settings(
+        &self,
+        quote_mint: &Pubkey,
+        quote_config: Option<&QuoteMintConfig>,
+    ) -> Result<FeeSettings> {
+        match quote_config {
+            // wrapped SOL is always priced by MainState, so callers can't pick between two fee sets
+            Some(config) if !is_native_mint(quote_mint) => {
+                require!(
+                    config.enabled && config.mint == *quote_mint,
+                    MemepodError::UnknownToken
+                );
+                Ok(FeeSettings {
+                    creation_fee: config.creation_fee,
+                    trading_fee: config.trading_fee,
+                    creator_fee: config.creator_fee,
+                    owner_fee: config.owner_fee,
+                })
+            }
+            _ => {
+                require!(is_native_mint(quote_mint), MemepodError::UnknownToken);
+                Ok(FeeSettings {
+                    creation_fee: self.creation_fee,
+                    trading_fee: self.trading_fee,
+                    creator_fee: self.creator_fee,
+                    owner_fee: self.owner_fee,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct FeeSettings {
+    pub creation_fee: u64,
+    pub trading_fee: u16,
+    pub creator_fee: u16,
+    pub owner_fee: u16,
+}
+
+// allowlisted quote mint with its own fees
+#[account]
+pub struct QuoteMintConfig {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub creation_fee: u64,
+    pub trading_fee: u16,
+    pub creator_fee: u16,
+    pub owner_fee: u16,
+    pub total_volume: u64, // quote mint units
+    pub fees_collected: u64, // quote mint units
+}
+
+impl QuoteMintConfig {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"quote_mint";
+}
+
+// protocol-wide counters, kept next to MainState
+#[account]
+pub struct ProtocolStats {
+    pub next_pod_id: u64,
+    pub pods_created: u64,
+    pub pods_closed: u64,
+    pub total_volume: u64, // lamports, spl quote mints are counted on their QuoteMintConfig
+    pub tokens_sold: u64,
+    pub fees_collected: u64, // lamports
+}
+
+impl ProtocolStats {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"stats";
+
+    pub fn registry_page(&self) -> u64 {
+        self.next_pod_id / PodRegistry::PAGE_SIZE as u64
+    }
+
+    // hands out the next pod id and records the pod in its registry page
+    pub fn register_pod(&mut self, registry: &mut PodRegistry, pod: Pubkey) -> Result<u64> {
+        let pod_id = self.next_pod_id;
+        let page = self.registry_page();
+        require!(
+            registry.count == 0 || registry.page == page,
+            MemepodError::InvalidRegistryPage
+        );
+        registry.page = page;
+        registry.pods[registry.count as usize] = pod;
+        registry.count += 1;
+
+        self.next_pod_id += 1;
+        self.pods_created += 1;
+        Ok(pod_id)
+    }
+
+    // quote totals go to the pod's QuoteMintConfig when it has one, so units never mix
+    pub fn record_buy(
+        &mut self,
+        quote_config: Option<&mut QuoteMintConfig>,
+        quote_amount: u64,
+        base_amount: u64,
+        fee: u64,
+    ) {
+        self.tokens_sold = self.tokens_sold.saturating_add(base_amount);
+        let (total_volume, fees_collected) = match quote_config {
+            Some(config) => (&mut config.total_volume, &mut config.fees_collected),
+            None => (&mut self.total_volume, &mut self.fees_collected),
+        };
+        *total_volume = total_volume.saturating_add(quote_amount);
+        *fees_collected = fees_collected.saturating_add(fee);
+    }
+}
+
+// fixed-size page of pod addresses, pod id `n` lives at `pods[n % PAGE_SIZE]` of page `n / PAGE_SIZE`
+#[account]
+pub struct PodRegistry {
+    pub page: u64,
+    pub count: u32,
+    pub pods: [Pubkey; PodRegistry::PAGE_SIZE],
+}
+
+impl PodRegistry {
+    pub const PAGE_SIZE: usize = 64;
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"registry";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::NATIVE_MINT_STR;
+    use std::str::FromStr;
+
+    fn quote_config(mint: Pubkey, trading_fee: u16) -> QuoteMintConfig {
+        QuoteMintConfig {
+            mint,
+            enabled: true,
+            creation_fee: 0,
+            trading_fee,
+            creator_fee: 0,
+            owner_fee: 0,
+            total_volume: 0,
+            fees_collected: 0,
+        }
+    }
+
+    fn main_state(trading_fee: u16) -> MainState {
+        let data = [0u8; MainState::MAX_SIZE];
+        let mut state = MainState::deserialize(&mut &data[..]).unwrap();
+        state.trading_fee = trading_fee;
+        state
+    }
+
+    #[test]
+    fn wrapped_sol_always_uses_main_state_fees() {
+        let native_mint = Pubkey::from_str(NATIVE_MINT_STR).unwrap();
+        let state = main_state(10);
+        let config = quote_config(native_mint, 1);
+        let fees = state.fee_settings(&native_mint, Some(&config)).unwrap();
+        assert_eq!(fees.trading_fee, 10);
+        let fees = state.fee_settings(&native_mint, None).unwrap();
+        assert_eq!(fees.trading_fee, 10);
+    }
+
+    #[test]
+    fn spl_quote_mints_need_their_config() {
+        let mint = Pubkey::new_unique();
+        let state = main_state(10);
+        assert!(state.fee_settings(&mint, None).is_err());
+        let config = quote_config(mint, 1);
+        let fees = state.fee_settings(&mint, Some(&config)).unwrap();
+        assert_eq!(fees.trading_fee, 1);
+    }
 }
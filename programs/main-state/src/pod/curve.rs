@@ -0,0 +1,473 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MemepodError, utils::sqrt_u128};
+
+// curve prices are quoted in lamports per PRICE_SCALE base units
+pub const PRICE_SCALE: u128 = 1_000_000_000;
+pub const GROWTH_BPS_DIV: u128 = 10_000;
+const FIXED_POINT_ONE: u128 = 1_000_000_000_000;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PodCurve {
+    /// flat sale at `PodState::token_price` base units per SOL
+    Fixed,
+    /// price = start_price + slope * bought_amount / PRICE_SCALE
+    Linear { start_price: u64, slope: u64 },
+    /// price grows by `growth_bps` every `step` base units bought
+    Exponential {
+        start_price: u64,
+        growth_bps: u16,
+        step: u64,
+    },
+    /// x * y = k on virtual reserves, the base reserve shrinking as tokens are bought
+    ConstantProduct {
+        virtual_quote: u64,
+        virtual_base: u64,
+    },
+}
+
+impl PodCurve {
+    pub fn validate(&self, token_price: u64, base_amount: u64) -> Result<()> {
+        let valid = match *self {
+            PodCurve::Fixed => token_price > 0,
+            PodCurve::Linear { start_price, .. } => start_price > 0,
+            PodCurve::Exponential {
+                start_price,
+                growth_bps,
+                step,
+            } => start_price > 0 && growth_bps > 0 && step > 0,
+            PodCurve::ConstantProduct {
+                virtual_quote,
+                virtual_base,
+            } => virtual_quote > 0 && virtual_base > base_amount,
+        };
+        require!(valid, MemepodError::InvalidCurve);
+        // the whole supply has to be priceable, otherwise buys near the end overflow
+        require!(
+            self.quote_for_base(token_price, 0, base_amount, true)
+                .is_ok(),
+            MemepodError::InvalidCurve
+        );
+        Ok(())
+    }
+
+    /// quote needed to move the sold supply from `bought` to `bought + base_amount`
+    pub fn quote_for_base(
+        &self,
+        token_price: u64,
+        bought: u64,
+        base_amount: u64,
+        round_up: bool,
+    ) -> Result<u64> {
+        let to = bought
+            .checked_add(base_amount)
+            .ok_or(MemepodError::MathOverflow)?;
+        let quote = match *self {
+            PodCurve::Fixed => div_round(
+                mul(base_amount as u128, PRICE_SCALE)?,
+                token_price as u128,
+                round_up,
+            )?,
+            PodCurve::ConstantProduct {
+                virtual_quote,
+                virtual_base,
+            } => {
+                require!(to < virtual_base, MemepodError::InsufficientFund);
+                let k = mul(virtual_quote as u128, virtual_base as u128)?;
+                let quote_before = div_round(k, (virtual_base - bought) as u128, !round_up)?;
+                let quote_after = div_round(k, (virtual_base - to) as u128, round_up)?;
+                quote_after.saturating_sub(quote_before)
+            }
+            _ => {
+                let scaled = self
+                    .cumulative_cost(to)?
+                    .checked_sub(self.cumulative_cost(bought)?)
+                    .ok_or(MemepodError::MathOverflow)?;
+                div_round(scaled, PRICE_SCALE, round_up)?
+            }
+        };
+        u64::try_from(quote).map_err(|_| MemepodError::MathOverflow.into())
+    }
+
+    /// largest base amount whose cost does not exceed `quote_amount`
+    pub fn base_for_quote(
+        &self,
+        token_price: u64,
+        bought: u64,
+        quote_amount: u64,
+        remaining: u64,
+    ) -> Result<u64> {
+        if let PodCurve::Fixed = self {
+            let base_amount = (quote_amount as u128)
+                .checked_mul(token_price as u128)
+                .unwrap()
+                .checked_div(PRICE_SCALE)
+                .unwrap();
+            require!(
+                base_amount <= remaining as u128,
+                MemepodError::InsufficientFund
+            );
+            return Ok(base_amount as u64);
+        }
+
+        let max_base = match *self {
+            PodCurve::ConstantProduct { virtual_base, .. } => {
+                remaining.min(virtual_base.saturating_sub(bought).saturating_sub(1))
+            }
+            _ => remaining,
+        };
+        require!(
+            self.quote_for_base(token_price, bought, max_base, true)? >= quote_amount,
+            MemepodError::InsufficientFund
+        );
+
+        let (mut low, mut high) = self.search_range(token_price, bought, quote_amount, max_base)?;
+        while low < high {
+            let mid = high - (high - low) / 2;
+            if self.quote_for_base(token_price, bought, mid, true)? <= quote_amount {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Ok(low)
+    }
+
+    // bounds for the base_for_quote search: a few units around the closed form where the
+    // curve has one, otherwise whatever the marginal prices at both ends allow
+    fn search_range(
+        &self,
+        token_price: u64,
+        bought: u64,
+        quote_amount: u64,
+        max_base: u64,
+    ) -> Result<(u64, u64)> {
+        if let Some(estimate) = self.inverse_estimate(bought, quote_amount)? {
+            let range = (
+                estimate.saturating_sub(2).min(max_base),
+                estimate.saturating_add(2).min(max_base),
+            );
+            if self.brackets(token_price, bought, quote_amount, range, max_base)? {
+                return Ok(range);
+            }
+        }
+        if !matches!(self, PodCurve::Linear { .. } | PodCurve::Exponential { .. }) {
+            return Ok((0, max_base));
+        }
+
+        // prices only rise, so the first unit is the cheapest and the last the dearest
+        let budget = mul(quote_amount as u128, PRICE_SCALE)?;
+        let high = u64::try_from(budget / self.marginal_price(bought)? + 1)
+            .unwrap_or(u64::MAX)
+            .min(max_base);
+        let low = u64::try_from(
+            mul(quote_amount.saturating_sub(1) as u128, PRICE_SCALE)?
+                / (self.marginal_price(bought + high)? + 1),
+        )
+        .unwrap_or(u64::MAX)
+        .saturating_sub(1)
+        .min(high);
+        if self.brackets(token_price, bought, quote_amount, (low, high), max_base)? {
+            return Ok((low, high));
+        }
+        Ok((0, max_base))
+    }
+
+    // whether the largest affordable amount lies within `range`
+    fn brackets(
+        &self,
+        token_price: u64,
+        bought: u64,
+        quote_amount: u64,
+        (low, high): (u64, u64),
+        max_base: u64,
+    ) -> Result<bool> {
+        if low > 0 && self.quote_for_base(token_price, bought, low, true)? > quote_amount {
+            return Ok(false);
+        }
+        Ok(high == max_base
+            || self.quote_for_base(token_price, bought, high + 1, true)? > quote_amount)
+    }
+
+    // closed form inverse of quote_for_base, None when the curve has none or it overflows
+    fn inverse_estimate(&self, bought: u64, quote_amount: u64) -> Result<Option<u64>> {
+        match *self {
+            PodCurve::ConstantProduct {
+                virtual_quote,
+                virtual_base,
+            } => {
+                // exact: ceil(k / reserve) may not pass quote_before + quote_amount
+                let k = mul(virtual_quote as u128, virtual_base as u128)?;
+                let base_reserve = (virtual_base - bought) as u128;
+                let quote_reserve = (k / base_reserve)
+                    .checked_add(quote_amount as u128)
+                    .ok_or(MemepodError::MathOverflow)?;
+                let base_left = div_round(k, quote_reserve, true)?;
+                Ok(Some(base_reserve.saturating_sub(base_left) as u64))
+            }
+            PodCurve::Linear { start_price, slope } => {
+                // largest supply n with start_price * n + slope * n^2 / (2 * PRICE_SCALE) <= target
+                let target = match self
+                    .cumulative_cost(bought)?
+                    .checked_add(mul(quote_amount as u128, PRICE_SCALE)?)
+                {
+                    Some(target) => target,
+                    None => return Ok(None),
+                };
+                let supply = if slope == 0 {
+                    target / start_price as u128
+                } else {
+                    let flat = PRICE_SCALE * start_price as u128;
+                    let discriminant = flat.checked_mul(flat).and_then(|flat_sq| {
+                        (2 * PRICE_SCALE)
+                            .checked_mul(slope as u128)?
+                            .checked_mul(target)?
+                            .checked_add(flat_sq)
+                    });
+                    match discriminant {
+                        Some(discriminant) => (sqrt_u128(discriminant) - flat) / slope as u128,
+                        None => return Ok(None),
+                    }
+                };
+                Ok(Some(
+                    u64::try_from(supply.saturating_sub(bought as u128)).unwrap_or(u64::MAX),
+                ))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // price of the next base unit once `supply` units are sold, in lamports per PRICE_SCALE
+    fn marginal_price(&self, supply: u64) -> Result<u128> {
+        match *self {
+            PodCurve::Linear { start_price, slope } => (start_price as u128)
+                .checked_add(mul_div(supply as u128, slope as u128, PRICE_SCALE)?)
+                .ok_or(MemepodError::MathOverflow.into()),
+            PodCurve::Exponential {
+                start_price,
+                growth_bps,
+                step,
+            } => mul_div(
+                start_price as u128,
+                pow_fixed(growth_ratio(growth_bps), (supply / step) as u128)?,
+                FIXED_POINT_ONE,
+            ),
+            _ => Err(MemepodError::InvalidCurve.into()),
+        }
+    }
+
+    // total cost of the first `supply` base units, in lamports * PRICE_SCALE
+    fn cumulative_cost(&self, supply: u64) -> Result<u128> {
+        let supply = supply as u128;
+        match *self {
+            PodCurve::Linear { start_price, slope } => {
+                let flat = mul(start_price as u128, supply)?;
+                // supply^2 always fits in a u128, the slope is applied after scaling down
+                let ramp = mul_div(supply * supply, slope as u128, 2 * PRICE_SCALE)?;
+                flat.checked_add(ramp)
+                    .ok_or(MemepodError::MathOverflow.into())
+            }
+            PodCurve::Exponential {
+                start_price,
+                growth_bps,
+                step,
+            } => {
+                let step = step as u128;
+                let steps = supply / step;
+                let ratio_pow = pow_fixed(growth_ratio(growth_bps), steps)?;
+
+                // sum of ratio^i for i < steps, as a geometric series
+                let series = mul(ratio_pow - FIXED_POINT_ONE, GROWTH_BPS_DIV)? / growth_bps as u128;
+                let full_steps = mul_div(mul(series, start_price as u128)?, step, FIXED_POINT_ONE)?;
+                let partial_step = mul_div(
+                    mul(supply - steps * step, start_price as u128)?,
+                    ratio_pow,
+                    FIXED_POINT_ONE,
+                )?;
+                full_steps
+                    .checked_add(partial_step)
+                    .ok_or(MemepodError::MathOverflow.into())
+            }
+            _ => Err(MemepodError::InvalidCurve.into()),
+        }
+    }
+}
+
+fn growth_ratio(growth_bps: u16) -> u128 {
+    FIXED_POINT_ONE + FIXED_POINT_ONE * growth_bps as u128 / GROWTH_BPS_DIV
+}
+
+fn mul(a: u128, b: u128) -> Result<u128> {
+    a.checked_mul(b).ok_or(MemepodError::MathOverflow.into())
+}
+
+// a * b / den rounded down, without overflowing on a * b when the result fits
+fn mul_div(a: u128, b: u128, den: u128) -> Result<u128> {
+    require!(den > 0, MemepodError::MathOverflow);
+    mul(a / den, b)?
+        .checked_add(mul(a % den, b)? / den)
+        .ok_or(MemepodError::MathOverflow.into())
+}
+
+fn div_round(num: u128, den: u128, round_up: bool) -> Result<u128> {
+    require!(den > 0, MemepodError::MathOverflow);
+    let quotient = num / den;
+    if round_up && quotient * den != num {
+        return Ok(quotient + 1);
+    }
+    Ok(quotient)
+}
+
+fn pow_fixed(mut base: u128, mut exp: u128) -> Result<u128> {
+    let mut result = FIXED_POINT_ONE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base)? / FIXED_POINT_ONE;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul(base, base)? / FIXED_POINT_ONE;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 800M tokens with 9 decimals
+    const SUPPLY: u64 = 800_000_000 * 1_000_000_000;
+
+    const CURVES: [PodCurve; 4] = [
+        PodCurve::Fixed,
+        PodCurve::Linear {
+            start_price: 30,
+            slope: 1,
+        },
+        PodCurve::Exponential {
+            start_price: 30,
+            growth_bps: 50,
+            step: 1_000_000_000_000_000,
+        },
+        PodCurve::ConstantProduct {
+            virtual_quote: 30_000_000_000,
+            virtual_base: 1_000_000_000_000_000_000,
+        },
+    ];
+    const TOKEN_PRICE: u64 = 30_000_000_000;
+
+    #[test]
+    fn curves_price_a_full_nine_decimal_supply() {
+        for curve in CURVES {
+            curve.validate(TOKEN_PRICE, SUPPLY).unwrap();
+            let bought = SUPPLY / 2;
+            let base_amount = SUPPLY - bought;
+            curve
+                .quote_for_base(TOKEN_PRICE, bought, base_amount, true)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn quote_and_base_round_trip() {
+        for curve in CURVES {
+            for bought in [0, SUPPLY / 3, SUPPLY / 2] {
+                let base_amount = 1_000_000_000_000;
+                let quote = curve
+                    .quote_for_base(TOKEN_PRICE, bought, base_amount, true)
+                    .unwrap();
+                let received = curve
+                    .base_for_quote(TOKEN_PRICE, bought, quote, SUPPLY - bought)
+                    .unwrap();
+                assert!(received >= base_amount, "{curve:?} at {bought}");
+                assert!(
+                    curve
+                        .quote_for_base(TOKEN_PRICE, bought, received, true)
+                        .unwrap()
+                        <= quote
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn curves_that_overflow_are_rejected() {
+        let steep_line = PodCurve::Linear {
+            start_price: 30,
+            slope: u64::MAX,
+        };
+        assert!(steep_line.validate(0, SUPPLY).is_err());
+
+        let steep_exponential = PodCurve::Exponential {
+            start_price: 30,
+            growth_bps: 10_000,
+            step: 1_000_000_000,
+        };
+        assert!(steep_exponential.validate(0, SUPPLY).is_err());
+
+        let free_start = PodCurve::Linear {
+            start_price: 0,
+            slope: 1,
+        };
+        assert!(free_start.validate(0, SUPPLY).is_err());
+    }
+
+    #[test]
+    fn exponential_cost_follows_the_geometric_series() {
+        // one lamport per base unit, 1% more every 1_000 units
+        let curve = PodCurve::Exponential {
+            start_price: PRICE_SCALE as u64,
+            growth_bps: 100,
+            step: 1_000,
+        };
+        // 1_000 * (1.01^10 - 1) / 0.01 = 10_462.21
+        assert_eq!(curve.quote_for_base(0, 0, 10_000, true).unwrap(), 10_463);
+        assert_eq!(curve.quote_for_base(0, 0, 10_000, false).unwrap(), 10_462);
+        // plus half a step at 1.01^10: 500 * 1.1046 = 552.31
+        assert_eq!(curve.quote_for_base(0, 0, 10_500, true).unwrap(), 11_015);
+        assert_eq!(curve.quote_for_base(0, 0, 10_500, false).unwrap(), 11_014);
+        // the 11th step on its own costs 1_000 * 1.01^10 = 1_104.62
+        assert_eq!(curve.quote_for_base(0, 10_000, 1_000, true).unwrap(), 1_105);
+    }
+
+    #[test]
+    fn bought_amount_is_the_most_the_quote_affords() {
+        let stepped = PodCurve::Exponential {
+            start_price: 30,
+            growth_bps: 100,
+            step: 1_000_000_000_000_000,
+        };
+        // too pricey for the closed form to fit in a u128
+        let pricey_line = PodCurve::Linear {
+            start_price: 20_000_000_000,
+            slope: 1,
+        };
+        for curve in CURVES.into_iter().skip(1).chain([stepped, pricey_line]) {
+            for bought in [0, SUPPLY / 3, SUPPLY - 1_000_000_000_000_000] {
+                for quote in [0, 1, 29, 1_000_000, 1_000_000_000, 5_000_000_000] {
+                    let remaining = SUPPLY - bought;
+                    if curve
+                        .quote_for_base(TOKEN_PRICE, bought, remaining, true)
+                        .map_or(true, |cost| cost < quote)
+                    {
+                        continue;
+                    }
+                    let received = curve
+                        .base_for_quote(TOKEN_PRICE, bought, quote, remaining)
+                        .unwrap();
+                    let cost = |base_amount| {
+                        curve
+                            .quote_for_base(TOKEN_PRICE, bought, base_amount, true)
+                            .unwrap()
+                    };
+                    assert!(received == 0 || cost(received) <= quote, "{curve:?} at {bought} for {quote}");
+                    assert!(
+                        received == remaining || cost(received + 1) > quote,
+                        "{curve:?} at {bought} for {quote}"
+                    );
+                }
+            }
+        }
+    }
+}
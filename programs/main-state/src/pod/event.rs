@@ -1,12 +1,26 @@
 use anchor_lang::prelude::*;
 
+use crate::{CreatorVesting, PodCurve, UnsoldPolicy, VestingSchedule};
+
 #[event]
 pub struct CreateEvent {
     pub creator: Pubkey,
     pub base_mint: Pubkey,
+    pub pod_index: u64,
+    pub pod_id: u64,
     pub base_amount: u64,
     pub token_price: u64,
+    pub curve: PodCurve,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub release_duration: i64,
+    pub buyer_vesting: Option<VestingSchedule>,
+    pub creator_vesting: Option<CreatorVesting>,
+    pub start_time: u64,
     pub expire_time: u64,
+    pub unsold_policy: UnsoldPolicy,
+    pub safety_flags: u8,
+    pub native_quote: bool,
     pub timestamp: i64,
 }
 
@@ -16,6 +30,34 @@ pub struct BuyEvent {
     pub base_mint: Pubkey,
     pub quote_amount: u64,
     pub base_amount: u64,
+    pub owner_fee: u64,
+    pub creator_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RescheduleEvent {
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub start_time: u64,
+    pub expire_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseEvent {
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub resume_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResumeEvent {
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub paused_duration: i64,
+    pub expire_time: u64,
     pub timestamp: i64,
 }
 
@@ -25,3 +67,78 @@ pub struct CompleteEvent {
     pub base_mint: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct SellEvent {
+    pub user: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_amount: u64,
+    pub base_amount: u64,
+    pub owner_fee: u64,
+    pub creator_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimEvent {
+    pub user: Pubkey,
+    pub base_mint: Pubkey,
+    pub base_amount: u64,
+    pub claimed_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorVestingClaimEvent {
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub base_amount: u64,
+    pub claimed_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FinalizeEvent {
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub bought_amount: u64,
+    pub unsold_amount: u64,
+    pub unsold_policy: UnsoldPolicy,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundEvent {
+    pub user: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_amount: u64,
+    pub base_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneVoteEvent {
+    pub pod: Pubkey,
+    pub voter: Pubkey,
+    pub index: u8,
+    pub approve: bool,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneResolvedEvent {
+    pub pod: Pubkey,
+    pub index: u8,
+    pub approved: bool,
+    pub released_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneReclaimEvent {
+    pub pod: Pubkey,
+    pub user: Pubkey,
+    pub quote_amount: u64,
+    pub timestamp: i64,
+}
@@ -2,100 +2,276 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
 };
 use crate::{
     error::MemepodError,
     main_state,
-    utils::{calculate_trading_fee, close_token_account, sync_native_amount},
-    BuyEvent, CompleteEvent, MainState, PodState,
+    utils::{
+        amount_after_transfer_fee, calculate_amount_with_trading_fee, calculate_trading_fee,
+        is_native_mint, split_trading_fee, sync_native_amount, transfer_lamports,
+        unwrap_native_account,
+    },
+    BuyEvent, CompleteEvent, FeeSettings, MainState, PodState, PodStatus, ProtocolStats, UserPosition,
+    QuoteMintConfig, PRICE_SCALE,
 };
 
-pub fn buy(ctx: Context<ABuy>, amount: u64) -> Result<()> {
-    let main_state = &mut ctx.accounts.main_state;
+pub fn buy(
+    ctx: Context<ABuy>,
+    amount: u64,
+    min_base_out: u64,
+    max_price: Option<u64>,
+) -> Result<()> {
+    let fees = check_buy_allowed(&ctx)?;
+    let pod_state = &mut ctx.accounts.pod_state;
+
+    let fee = calculate_trading_fee(fees.trading_fee, amount);
+    let input_amount = amount - fee;
+    let output_amount = pod_state.compute_receivable_amount_on_buy(input_amount)?;
+    require!(
+        output_amount >= min_base_out,
+        MemepodError::SlippageExceeded
+    );
+    if let Some(max_price) = max_price {
+        // average price paid, in lamports per PRICE_SCALE base units
+        let price = (input_amount as u128)
+            .checked_mul(PRICE_SCALE)
+            .unwrap()
+            .checked_div(output_amount as u128)
+            .unwrap_or(u128::MAX);
+        require!(
+            price <= max_price as u128,
+            MemepodError::PriceLimitExceeded
+        );
+    }
+
+    settle_buy(ctx, fees, amount, fee, output_amount)
+}
+
+pub fn buy_exact_out(ctx: Context<ABuy>, base_amount: u64, max_quote_in: u64) -> Result<()> {
+    let fees = check_buy_allowed(&ctx)?;
+    let pod_state = &mut ctx.accounts.pod_state;
+
+    let input_amount = pod_state.compute_payable_amount_on_buy(base_amount)?;
+    let amount = calculate_amount_with_trading_fee(fees.trading_fee, input_amount);
+    require!(amount <= max_quote_in, MemepodError::SlippageExceeded);
+    let fee = calculate_trading_fee(fees.trading_fee, amount);
+
+    settle_buy(ctx, fees, amount, fee, base_amount)
+}
+
+fn check_buy_allowed(ctx: &Context<ABuy>) -> Result<FeeSettings> {
+    let main_state = &ctx.accounts.main_state;
     require!(
         main_state.initialized.eq(&true),
         MemepodError::Uninitialized
     );
+    main_state.require_not_paused(MainState::PAUSE_BUY)?;
 
-    let pod_state = &mut ctx.accounts.pod_state;
-    require!(pod_state.is_active.eq(&true), MemepodError::NotActive);
+    let pod_state = &ctx.accounts.pod_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(pod_state.is_started(now), MemepodError::PodNotStarted);
+    require!(!pod_state.is_paused(now), MemepodError::PodPaused);
+    require!(
+        pod_state.effective_status(now) == PodStatus::Live,
+        MemepodError::NotActive
+    );
+    require!(!pod_state.is_expired(now), MemepodError::PodExpired);
+    main_state.fee_settings(
+        &pod_state.quote_mint,
+        ctx.accounts.quote_config.as_deref().map(|config| &**config),
+    )
+}
 
+// moves `amount` of quote from the buyer, `fee` of which is trading fee, for `output_amount` of base
+fn settle_buy(
+    ctx: Context<ABuy>,
+    fees: FeeSettings,
+    amount: u64,
+    fee: u64,
+    output_amount: u64,
+) -> Result<()> {
+    let pod_state = &mut ctx.accounts.pod_state;
     let buyer = ctx.accounts.buyer.to_account_info();
     let buyer_base_ata = &ctx.accounts.buyer_base_ata;
-    let buyer_quote_ata = &ctx.accounts.buyer_quote_ata;
     let token_program = ctx.accounts.token_program.to_account_info();
     let system_program = ctx.accounts.system_program.to_account_info();
 
-    sync_native_amount(
-        buyer.clone(),
-        &buyer_quote_ata,
-        amount,
-        system_program.clone(),
-        token_program.clone(),
-    )?;
-
-    let fee = calculate_trading_fee(main_state.trading_fee, amount);
+    let (owner_fee, creator_fee) =
+        split_trading_fee(fee, fees.owner_fee, fees.creator_fee);
     let input_amount = amount - fee;
-    let output_amount = pod_state.compute_receivable_amount_on_buy(input_amount);
+    require!(
+        pod_state.raised_amount + input_amount <= pod_state.hard_cap,
+        MemepodError::HardCapReached
+    );
+    pod_state.raised_amount += input_amount;
 
-    // sending fee
-    let fee_transfer_cpi_account = Transfer {
-        from: buyer_quote_ata.to_account_info(),
-        to: ctx.accounts.fee_quote_ata.to_account_info(),
-        authority: buyer.clone(),
-    };
-    token::transfer(
-        CpiContext::new(token_program.clone(), fee_transfer_cpi_account),
-        fee / 2,
-    )?;
-
-    // sending input amount (sol)
-    let input_amount_transfer_cpi_account = Transfer {
-        from: buyer_quote_ata.to_account_info(),
-        to: ctx.accounts.reserver_quote_ata.to_account_info(),
-        authority: buyer.clone(),
+    let now = Clock::get()?.unix_timestamp;
+    pod_state.start_if_due(now)?;
+    pod_state.resume_if_due(now)?;
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.pod = pod_state.key();
+    user_position.owner = buyer.key();
+    // positions track what actually reaches the buyer once transfer fees are withheld
+    let base_received = if pod_state.is_claim_mode() {
+        output_amount
+    } else {
+        amount_after_transfer_fee(&ctx.accounts.base_mint, output_amount)?
     };
-    token::transfer(
-        CpiContext::new(token_program.clone(), input_amount_transfer_cpi_account),
-        input_amount + fee / 2,
-    )?;
-
-    // sending tokens from reserve ata (meme)
-    let output_amount_transfer_cpi_account = Transfer {
-        from: ctx.accounts.reserver_base_ata.to_account_info(),
-        to: buyer_base_ata.to_account_info(),
-        authority: pod_state.to_account_info(),
-    };
-    token::transfer(
-        CpiContext::new_with_signer(
-            token_program.clone(),
-            output_amount_transfer_cpi_account,
-            &[&[
-                PodState::PREFIX_SEED,
-                pod_state.base_mint.as_ref(),
-                pod_state.quote_mint.as_ref(),
-                pod_state.owner.as_ref(),
-                &[ctx.bumps.pod_state],
-            ]],
-        ),
+    user_position.record_buy(amount, base_received, fee, now);
+    pod_state.held_base_amount += base_received;
+    let quote_config = ctx
+        .accounts
+        .quote_config
+        .as_deref_mut()
+        .filter(|_| !is_native_mint(&pod_state.quote_mint));
+    ctx.accounts.protocol_stats.record_buy(
+        quote_config.map(|config| &mut **config),
+        amount,
         output_amount,
-    )?;
+        fee,
+    );
+
+    if pod_state.native_quote {
+        // native pods take lamports straight from the buyer, no wsol round-trip
+        let quote_vault = ctx
+            .accounts
+            .quote_vault
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        transfer_lamports(
+            buyer.clone(),
+            ctx.accounts.fee_recipient.to_account_info(),
+            owner_fee,
+            system_program.clone(),
+            &[],
+        )?;
+        transfer_lamports(
+            buyer.clone(),
+            ctx.accounts.creator.to_account_info(),
+            creator_fee,
+            system_program.clone(),
+            &[],
+        )?;
+        transfer_lamports(
+            buyer.clone(),
+            quote_vault.to_account_info(),
+            input_amount,
+            system_program.clone(),
+            &[],
+        )?;
+    } else {
+        let buyer_quote_ata = ctx
+            .accounts
+            .buyer_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let fee_quote_ata = ctx
+            .accounts
+            .fee_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let creator_quote_ata = ctx
+            .accounts
+            .creator_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let reserver_quote_ata = ctx
+            .accounts
+            .reserver_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+
+        sync_native_amount(
+            buyer.clone(),
+            buyer_quote_ata,
+            amount,
+            system_program.clone(),
+            token_program.clone(),
+        )?;
 
-    // unwrap sol (or closing token account)
-    close_token_account(
-        buyer.clone(),
-        buyer_quote_ata.to_account_info(),
-        token_program,
-    )?;
+        // sending owner fee
+        let fee_transfer_cpi_account = Transfer {
+            from: buyer_quote_ata.to_account_info(),
+            to: fee_quote_ata.to_account_info(),
+            authority: buyer.clone(),
+        };
+        token::transfer(
+            CpiContext::new(token_program.clone(), fee_transfer_cpi_account),
+            owner_fee,
+        )?;
+
+        // sending creator fee
+        let creator_fee_transfer_cpi_account = Transfer {
+            from: buyer_quote_ata.to_account_info(),
+            to: creator_quote_ata.to_account_info(),
+            authority: buyer.clone(),
+        };
+        token::transfer(
+            CpiContext::new(token_program.clone(), creator_fee_transfer_cpi_account),
+            creator_fee,
+        )?;
+
+        // sending input amount (sol)
+        let input_amount_transfer_cpi_account = Transfer {
+            from: buyer_quote_ata.to_account_info(),
+            to: reserver_quote_ata.to_account_info(),
+            authority: buyer.clone(),
+        };
+        token::transfer(
+            CpiContext::new(token_program.clone(), input_amount_transfer_cpi_account),
+            input_amount,
+        )?;
+
+        // unwrap sol
+        unwrap_native_account(buyer.clone(), buyer_quote_ata, token_program)?;
+    }
+
+    // sending tokens from reserve ata (meme), claim mode only records the allocation
+    if !pod_state.is_claim_mode() {
+        let output_amount_transfer_cpi_account = TransferChecked {
+            from: ctx.accounts.reserver_base_ata.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
+            to: buyer_base_ata.to_account_info(),
+            authority: pod_state.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.base_token_program.to_account_info(),
+                output_amount_transfer_cpi_account,
+                &[&[
+                    PodState::PREFIX_SEED,
+                    pod_state.base_mint.as_ref(),
+                    pod_state.quote_mint.as_ref(),
+                    pod_state.owner.as_ref(),
+                    &pod_state.index.to_le_bytes(),
+                    &[ctx.bumps.pod_state],
+                ]],
+            ),
+            output_amount,
+            ctx.accounts.base_mint.decimals,
+        )?;
+    }
 
     emit!(BuyEvent {
         user: buyer.key(),
         base_mint: pod_state.base_mint,
         base_amount: output_amount,
         quote_amount: amount,
-        timestamp: Clock::get()?.unix_timestamp,
+        owner_fee,
+        creator_fee,
+        timestamp: now,
     });
 
+    if pod_state.is_target_reached() {
+        pod_state.transition(PodStatus::SoldOut)?;
+        emit!(CompleteEvent {
+            user: buyer.key(),
+            base_mint: pod_state.base_mint,
+            timestamp: now,
+        });
+    }
+
     Ok(())
 }
 
@@ -111,6 +287,12 @@ pub struct ABuy<'info> {
         bump,
     )]
     pub main_state: Box<Account<'info, MainState>>,
+    #[account(
+        mut,
+        seeds = [ProtocolStats::PREFIX_SEED],
+        bump,
+    )]
+    pub protocol_stats: Box<Account<'info, ProtocolStats>>,
 
     #[account(mut, address = main_state.fee_recipient,)]
     /// CHECK: this should be set by admin
@@ -122,7 +304,14 @@ pub struct ABuy<'info> {
         associated_token::authority = fee_recipient,
     )]
     /// CHECK: this should be set by fee_recipient
-    pub fee_quote_ata: Box<Account<'info, TokenAccount>>,
+    pub fee_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
 
     #[account(
         mut,
@@ -130,24 +319,45 @@ pub struct ABuy<'info> {
             PodState::PREFIX_SEED,
             base_mint.key().as_ref(), 
             quote_mint.key().as_ref(),
-            creator.key().as_ref()
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
         ],
         bump,
     )]
     pub pod_state: Box<Account<'info, PodState>>,
 
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        seeds = [
+            UserPosition::PREFIX_SEED,
+            pod_state.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump,
+        space = 8 + UserPosition::MAX_SIZE
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
     #[account(address = pod_state.base_mint)]
-    pub base_mint: Box<Account<'info, Mint>>,
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
     #[account(address = pod_state.quote_mint)]
     pub quote_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [QuoteMintConfig::PREFIX_SEED, quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub quote_config: Option<Box<Account<'info, QuoteMintConfig>>>,
 
     #[account(
         init_if_needed,
         payer = buyer,
         associated_token::mint = base_mint,
         associated_token::authority = buyer,
+        associated_token::token_program = base_token_program,
     )]
-    pub buyer_base_ata: Box<Account<'info, TokenAccount>>,
+    pub buyer_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(
         init_if_needed,
@@ -155,15 +365,16 @@ pub struct ABuy<'info> {
         associated_token::mint = quote_mint,
         associated_token::authority = buyer,
     )]
-    pub buyer_quote_ata: Box<Account<'info, TokenAccount>>,
+    pub buyer_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
 
     #[account(
         init_if_needed,
         payer = buyer,
         associated_token::mint = base_mint,
         associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
     )]
-    pub reserver_base_ata: Box<Account<'info, TokenAccount>>,
+    pub reserver_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
     #[account(
         init_if_needed,
@@ -171,9 +382,17 @@ pub struct ABuy<'info> {
         associated_token::mint = quote_mint,
         associated_token::authority = pod_state,
     )]
-    pub reserver_quote_ata: Box<Account<'info, TokenAccount>>,
+    pub reserver_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    // lamport vault, passed instead of the quote token accounts for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub quote_vault: Option<SystemAccount<'info>>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::{error::MemepodError, ClaimEvent, MainState, PodState, UserPosition};
+
+pub fn claim(ctx: Context<AClaim>, base_amount: u64) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_CLAIM)?;
+
+    let pod_state = &ctx.accounts.pod_state;
+    require!(pod_state.is_succeeded(), MemepodError::PodNotFinished);
+
+    let now = Clock::get()?.unix_timestamp;
+    let user_position = &mut ctx.accounts.user_position;
+    let claimable_amount = pod_state.claimable_amount(user_position, now);
+    require!(
+        base_amount > 0 && base_amount <= claimable_amount,
+        MemepodError::NothingToClaim
+    );
+    user_position.claimed_amount += base_amount;
+
+    // sending vested tokens from reserve ata (meme)
+    let base_transfer_cpi_account = TransferChecked {
+        from: ctx.accounts.reserver_base_ata.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        to: ctx.accounts.buyer_base_ata.to_account_info(),
+        authority: pod_state.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.base_token_program.to_account_info(),
+            base_transfer_cpi_account,
+            &[&[
+                PodState::PREFIX_SEED,
+                pod_state.base_mint.as_ref(),
+                pod_state.quote_mint.as_ref(),
+                pod_state.owner.as_ref(),
+                &pod_state.index.to_le_bytes(),
+                &[ctx.bumps.pod_state],
+            ]],
+        ),
+        base_amount,
+        ctx.accounts.base_mint.decimals,
+    )?;
+
+    emit!(ClaimEvent {
+        user: ctx.accounts.buyer.key(),
+        base_mint: pod_state.base_mint,
+        base_amount,
+        claimed_amount: user_position.claimed_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AClaim<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub creator: SystemAccount<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            UserPosition::PREFIX_SEED,
+            pod_state.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(address = pod_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = pod_state.quote_mint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = base_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = base_token_program,
+    )]
+    pub buyer_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
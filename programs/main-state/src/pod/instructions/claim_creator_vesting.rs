@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::{error::MemepodError, CreatorVestingClaimEvent, MainState, PodState, PodStatus};
+
+pub fn claim_creator_vesting(ctx: Context<AClaimCreatorVesting>, base_amount: u64) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_CLAIM)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    require!(
+        pod_state.is_settled() || pod_state.status == PodStatus::Closed,
+        MemepodError::PodNotFinished
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        base_amount > 0 && base_amount <= pod_state.creator_claimable_amount(now),
+        MemepodError::NothingToClaim
+    );
+    pod_state.creator_vesting_claimed += base_amount;
+
+    // sending vested team tokens from the vault (meme)
+    let base_transfer_cpi_account = TransferChecked {
+        from: ctx.accounts.creator_vesting_vault.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        to: ctx.accounts.creator_base_ata.to_account_info(),
+        authority: pod_state.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.base_token_program.to_account_info(),
+            base_transfer_cpi_account,
+            &[&[
+                PodState::PREFIX_SEED,
+                pod_state.base_mint.as_ref(),
+                pod_state.quote_mint.as_ref(),
+                pod_state.owner.as_ref(),
+                &pod_state.index.to_le_bytes(),
+                &[ctx.bumps.pod_state],
+            ]],
+        ),
+        base_amount,
+        ctx.accounts.base_mint.decimals,
+    )?;
+
+    emit!(CreatorVestingClaimEvent {
+        creator: pod_state.owner,
+        base_mint: pod_state.base_mint,
+        base_amount,
+        claimed_amount: pod_state.creator_vesting_claimed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AClaimCreatorVesting<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(address = pod_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = pod_state.quote_mint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [PodState::VESTING_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = pod_state,
+        token::token_program = base_token_program,
+    )]
+    pub creator_vesting_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = base_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = base_token_program,
+    )]
+    pub creator_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
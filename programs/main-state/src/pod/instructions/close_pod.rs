@@ -1,21 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::burn;
+use anchor_spl::token_interface::burn;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer, Burn},
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface},
 };
-use crate::{error::MemepodError, MainState, PodState};
+use crate::{error::MemepodError, MainState, PodState, PodStatus, ProtocolStats};
 use std::str::FromStr;
 
 pub fn close_pod(ctx: Context<AClosePodState>) -> Result<()> {
     let admin = ctx.accounts.admin.to_account_info();
 
     let main_state = &ctx.accounts.main_state;
-    let token_program = ctx.accounts.token_program.to_account_info();
+    let base_token_program = ctx.accounts.base_token_program.to_account_info();
     require!(
         main_state.initialized.eq(&true),
         MemepodError::Uninitialized
     );
+    main_state.require_not_paused(MainState::PAUSE_MANAGE)?;
     let pod_state = &mut ctx.accounts.pod_state;
 
     require!(
@@ -23,9 +24,12 @@ pub fn close_pod(ctx: Context<AClosePodState>) -> Result<()> {
         MemepodError::Unauthorised
     );
 
-    require!(pod_state.is_active.eq(&true), MemepodError::NotActive);
+    require!(pod_state.is_open(), MemepodError::NotActive);
+    // once buyers have paid in, the pod has to settle through finalize so the sol stays reachable
+    require!(pod_state.raised_amount == 0, MemepodError::PodHasBuyers);
 
-    pod_state.is_active = false;
+    pod_state.transition(PodStatus::Closed)?;
+    ctx.accounts.protocol_stats.pods_closed += 1;
 
     let cpi_accounts = Burn {
         mint: ctx.accounts.base_mint.to_account_info(),
@@ -33,16 +37,19 @@ pub fn close_pod(ctx: Context<AClosePodState>) -> Result<()> {
         authority: pod_state.to_account_info(),
     };
 
-    let amount = pod_state.base_amount - pod_state.bought_amount; 
+    let amount =
+        pod_state.base_amount - pod_state.bought_amount + pod_state.liquidity_base_amount;
+    pod_state.liquidity_base_amount = 0;
 
     burn(CpiContext::new_with_signer(
-                token_program.clone(),
+                base_token_program.clone(),
                 cpi_accounts,
                 &[&[
                     PodState::PREFIX_SEED,
                     pod_state.base_mint.as_ref(),
                     pod_state.quote_mint.as_ref(),
                     pod_state.owner.as_ref(),
+                    &pod_state.index.to_le_bytes(),
                     &[ctx.bumps.pod_state],
                 ][..]],
             ), amount)?;
@@ -60,6 +67,12 @@ pub struct AClosePodState<'info> {
         bump,
     )]
     pub main_state: Box<Account<'info, MainState>>,
+    #[account(
+        mut,
+        seeds = [ProtocolStats::PREFIX_SEED],
+        bump,
+    )]
+    pub protocol_stats: Box<Account<'info, ProtocolStats>>,
 
     #[account(
         mut,
@@ -68,26 +81,28 @@ pub struct AClosePodState<'info> {
             base_mint.key().as_ref(), 
             quote_mint.key().as_ref(),
             admin.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
         ],
         bump,
     )]
     pub pod_state: Box<Account<'info, PodState>>,
 
     #[account(mut,)]
-    pub base_mint: Box<Account<'info, Mint>>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
     
     #[account(mut,)]
-    pub quote_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         init_if_needed,
         payer = admin,
         associated_token::mint = base_mint,
         associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
     )]
-    pub reserver_base_ata: Box<Account<'info, TokenAccount>>,
+    pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 
 }
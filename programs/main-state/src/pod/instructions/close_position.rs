@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::{error::MemepodError, MainState, PodMilestones, PodState, UserPosition};
+
+pub fn close_position(ctx: Context<AClosePosition>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_CLAIM)?;
+
+    let pod_state = &ctx.accounts.pod_state;
+    require!(!pod_state.is_open(), MemepodError::PodNotFinished);
+
+    // keep the position around while it can still back a refund
+    let user_position = &ctx.accounts.user_position;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !pod_state.is_refundable(now) || user_position.remaining_base() == 0,
+        MemepodError::PodNotFinished
+    );
+    // and while it still has tokens to claim
+    require!(
+        !pod_state.is_claim_mode()
+            || !pod_state.is_succeeded()
+            || user_position.claimed_amount >= user_position.remaining_base(),
+        MemepodError::PodNotFinished
+    );
+    // and while it can still vote on milestones or reclaim a withheld tranche
+    let pod_milestones = &ctx.accounts.pod_milestones;
+    if !pod_milestones.data_is_empty() {
+        require_keys_eq!(*pod_milestones.owner, crate::ID);
+        let pod_milestones =
+            PodMilestones::try_deserialize(&mut &pod_milestones.try_borrow_data()?[..])?;
+        require!(
+            !pod_milestones.needs_position(user_position),
+            MemepodError::PodNotFinished
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AClosePosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    pub pod_state: Box<Account<'info, PodState>>,
+    /// CHECK: empty when the pod has no milestones, loaded as PodMilestones otherwise
+    #[account(
+        seeds = [PodMilestones::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub pod_milestones: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            UserPosition::PREFIX_SEED,
+            pod_state.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        has_one = owner @ MemepodError::Unauthorised,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+}
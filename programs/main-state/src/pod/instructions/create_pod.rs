@@ -2,23 +2,56 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, SyncNative, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
 };
 use crate::{
-    constants::NATIVE_MINT_STR,
+    constants::BPS_DIV,
     error::MemepodError,
-    utils::{check_balance_on_pod_creator, sync_native_amount},
-    CreateEvent, MainState, PodState,
+    utils::{
+        amount_after_transfer_fee, check_balance_on_pod_creator, is_native_mint,
+        sync_native_amount, transfer_lamports,
+    },
+    CreateEvent, CreatorState, CreatorVesting, LpLockPolicy, MainState, Milestone, PodCurve,
+    PodMilestones, PodRegistry, PodState, PodStatus, ProtocolStats, QuoteMintConfig,
+    UnsoldPolicy, VestingSchedule, MAX_MILESTONES,
 };
 
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MilestoneInput {
+    pub amount: u64,
+    pub description_hash: [u8; 32],
+    pub deadline: i64,
+}
+
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct CreatePodInput {
     pub pod_name: String,
     pub base_amount: u64,
     pub token_price: u64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub liquidity_base_amount: u64,
+    pub liquidity_bps: u16,
+    pub lp_lock_policy: LpLockPolicy,
+    pub release_duration: i64,
+    pub buyer_vesting: Option<VestingSchedule>,
+    pub creator_vesting: Option<CreatorVesting>,
     pub token_name: String,
     pub token_symbol: String,
     pub token_decimal: u8,
+    pub start_time: u64,
     pub expire_time: u64,
+    pub curve: PodCurve,
+    pub unsold_policy: UnsoldPolicy,
+    pub require_revoked_authorities: bool,
+    pub native_quote: bool,
+    pub milestones: Vec<MilestoneInput>,
+}
+
+impl CreatePodInput {
+    pub fn creator_vesting_amount(&self) -> u64 {
+        self.creator_vesting.map_or(0, |vesting| vesting.amount)
+    }
 }
 
 const MAX_POD_NAME_LEN: usize = 32;
@@ -33,59 +66,299 @@ fn str_to_fixed_bytes<const N: usize>(s: &str) -> [u8; N] {
     buf
 }
 
+// the mints a pod trades, as read from the accounts of whichever create instruction is used
+pub(crate) struct PodMints {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub decimal: u8,
+    pub safety_flags: u8,
+}
+
+pub(crate) fn init_pod_state(
+    pod_state: &mut PodState,
+    creator: Pubkey,
+    mints: PodMints,
+    input: &CreatePodInput,
+    now: i64,
+) -> Result<()> {
+    let PodMints {
+        base_mint,
+        quote_mint,
+        decimal,
+        safety_flags,
+    } = mints;
+    require!(input.pod_name.len() <= MAX_POD_NAME_LEN, MemepodError::PodNameTooLong);
+    require!(input.token_name.len() <= MAX_TOKEN_NAME_LEN, MemepodError::TokenNameTooLong);
+    require!(input.token_symbol.len() <= MAX_TOKEN_SYMBOL_LEN, MemepodError::TokenSymbolTooLong);
+    input.curve.validate(input.token_price, input.base_amount)?;
+    require!(
+        input.hard_cap > 0 && input.soft_cap <= input.hard_cap,
+        MemepodError::InvalidCap
+    );
+    require!(
+        input.liquidity_bps as u128 <= BPS_DIV,
+        MemepodError::InvalidLiquidityShare
+    );
+    if let LpLockPolicy::Timed { duration } = input.lp_lock_policy {
+        require!(duration > 0, MemepodError::InvalidLockDuration);
+    }
+    require!(
+        input.release_duration >= 0,
+        MemepodError::InvalidLockDuration
+    );
+    if let Some(schedule) = input.buyer_vesting {
+        schedule.validate()?;
+    }
+    if let Some(vesting) = input.creator_vesting {
+        require!(vesting.amount > 0, MemepodError::InvalidVestingSchedule);
+        vesting.schedule.validate()?;
+    }
+    require!(
+        input.expire_time > now as u64 && input.expire_time > input.start_time,
+        MemepodError::InvalidExpireTime
+    );
+    if input.native_quote {
+        require!(is_native_mint(&quote_mint), MemepodError::UnknownToken);
+    }
+    if input.require_revoked_authorities {
+        require!(
+            safety_flags & PodState::MINT_AUTHORITY_REVOKED != 0,
+            MemepodError::MintAuthorityNotRevoked
+        );
+        require!(
+            safety_flags & PodState::FREEZE_AUTHORITY_REVOKED != 0,
+            MemepodError::FreezeAuthorityNotRevoked
+        );
+    }
+
+    pod_state.owner = creator;
+    pod_state.base_mint = base_mint;
+    pod_state.quote_mint = quote_mint;
+    pod_state.native_quote = input.native_quote;
+    pod_state.base_amount = input.base_amount;
+    pod_state.pod_name = str_to_fixed_bytes(&input.pod_name);
+    pod_state.token_name = str_to_fixed_bytes(&input.token_name);
+    pod_state.token_symbol = str_to_fixed_bytes(&input.token_symbol);
+    pod_state.token_price = input.token_price;
+    pod_state.curve = input.curve;
+    pod_state.soft_cap = input.soft_cap;
+    pod_state.hard_cap = input.hard_cap;
+    pod_state.liquidity_base_amount = input.liquidity_base_amount;
+    pod_state.liquidity_bps = input.liquidity_bps;
+    pod_state.lp_lock_policy = input.lp_lock_policy;
+    pod_state.release_duration = input.release_duration;
+    pod_state.buyer_vesting = input.buyer_vesting;
+    pod_state.creator_vesting = input.creator_vesting;
+    pod_state.start_time = input.start_time;
+    pod_state.expire_time = input.expire_time;
+    pod_state.unsold_policy = input.unsold_policy;
+    pod_state.decimal = decimal;
+    pod_state.safety_flags = safety_flags;
+    pod_state.status = if input.start_time > now as u64 {
+        PodStatus::Scheduled
+    } else {
+        PodStatus::Live
+    };
+    Ok(())
+}
+
+// milestones are part of the sale terms, so they can only be set when the pod is created
+pub(crate) fn init_pod_milestones(
+    pod_state: &mut PodState,
+    pod: Pubkey,
+    pod_milestones: Option<&mut PodMilestones>,
+    milestones: &[MilestoneInput],
+) -> Result<()> {
+    let Some(pod_milestones) = pod_milestones else {
+        require!(milestones.is_empty(), MemepodError::InvalidMilestones);
+        return Ok(());
+    };
+    require!(
+        !milestones.is_empty() && milestones.len() <= MAX_MILESTONES,
+        MemepodError::InvalidMilestones
+    );
+
+    let mut total_amount: u64 = 0;
+    pod_milestones.pod = pod;
+    pod_milestones.count = milestones.len() as u8;
+    for (index, input) in milestones.iter().enumerate() {
+        require!(
+            input.amount > 0 && input.deadline > pod_state.expire_time as i64,
+            MemepodError::InvalidMilestones
+        );
+        total_amount = total_amount
+            .checked_add(input.amount)
+            .ok_or(MemepodError::MathOverflow)?;
+        pod_milestones.milestones[index] = Milestone {
+            amount: input.amount,
+            description_hash: input.description_hash,
+            deadline: input.deadline,
+            ..Default::default()
+        };
+    }
+    require!(
+        total_amount <= pod_state.hard_cap,
+        MemepodError::InvalidMilestones
+    );
+    pod_state.milestone_amount = total_amount;
+    Ok(())
+}
+
+pub(crate) fn emit_create_event(pod_state: &PodState, now: i64) {
+    emit!(CreateEvent {
+        creator: pod_state.owner,
+        base_mint: pod_state.base_mint,
+        pod_index: pod_state.index,
+        pod_id: pod_state.pod_id,
+        base_amount: pod_state.base_amount,
+        token_price: pod_state.token_price,
+        curve: pod_state.curve,
+        soft_cap: pod_state.soft_cap,
+        hard_cap: pod_state.hard_cap,
+        release_duration: pod_state.release_duration,
+        buyer_vesting: pod_state.buyer_vesting,
+        creator_vesting: pod_state.creator_vesting,
+        start_time: pod_state.start_time,
+        expire_time: pod_state.expire_time,
+        unsold_policy: pod_state.unsold_policy,
+        safety_flags: pod_state.safety_flags,
+        native_quote: pod_state.native_quote,
+        timestamp: now
+    });
+}
+
+// native-quote pods keep their vault rent exempt so buys of any size can land in it
+pub(crate) fn fund_quote_vault<'info>(
+    pod_state: &PodState,
+    quote_vault: Option<&SystemAccount<'info>>,
+    creator: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    if !pod_state.native_quote {
+        return Ok(());
+    }
+    let quote_vault = quote_vault.ok_or(MemepodError::MissingQuoteAccount)?;
+    let rent_amount = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(quote_vault.lamports());
+    transfer_lamports(creator, quote_vault.to_account_info(), rent_amount, system_program, &[])
+}
+
 pub fn create_pod(ctx: Context<ACreatePod>, input: CreatePodInput) -> Result<()> {
     let main_state = &mut ctx.accounts.main_state;
     require!(
         main_state.initialized.eq(&true),
         MemepodError::Uninitialized
     );
+    main_state.require_not_paused(MainState::PAUSE_CREATE)?;
+    let fees = main_state.fee_settings(
+        &ctx.accounts.quote_mint.key(),
+        ctx.accounts.quote_config.as_deref().map(|config| &**config),
+    )?;
 
-    require!(input.pod_name.len() <= MAX_POD_NAME_LEN, MemepodError::PodNameTooLong);
-    require!(input.token_name.len() <= MAX_TOKEN_NAME_LEN, MemepodError::TokenNameTooLong);
-    require!(input.token_symbol.len() <= MAX_TOKEN_SYMBOL_LEN, MemepodError::TokenSymbolTooLong);
-
+    let now = Clock::get()?.unix_timestamp;
     let pod_state = &mut ctx.accounts.pod_state;
     let creator = ctx.accounts.creator.to_account_info();
     let system_program = ctx.accounts.system_program.to_account_info();
     let token_program = ctx.accounts.token_program.to_account_info();
+    let base_token_program = ctx.accounts.base_token_program.to_account_info();
+    let base_mint = &ctx.accounts.base_mint;
     let creator_base_ata = &ctx.accounts.creator_base_ata;
     let creator_quote_ata = &ctx.accounts.creator_quote_ata;
 
-    pod_state.owner = creator.key();
-    pod_state.base_mint = creator_base_ata.mint;
-    pod_state.quote_mint = creator_quote_ata.mint;
-    pod_state.base_amount = input.base_amount;
-    pod_state.pod_name = str_to_fixed_bytes(&input.pod_name);
-    pod_state.token_name = str_to_fixed_bytes(&input.token_name);
-    pod_state.token_symbol = str_to_fixed_bytes(&input.token_symbol);
-    pod_state.token_price = input.token_price;
-    pod_state.expire_time = input.expire_time;
-    pod_state.decimal = input.token_decimal;
-    pod_state.is_active = true;
+    init_pod_state(
+        pod_state,
+        creator.key(),
+        PodMints {
+            base_mint: creator_base_ata.mint,
+            quote_mint: creator_quote_ata.mint,
+            decimal: base_mint.decimals,
+            safety_flags: PodState::compute_safety_flags(base_mint),
+        },
+        &input,
+        now,
+    )?;
+    pod_state.index = ctx.accounts.creator_state.next_index(creator.key());
+    pod_state.pod_id = ctx
+        .accounts
+        .protocol_stats
+        .register_pod(&mut ctx.accounts.pod_registry, pod_state.key())?;
+    let pod_key = pod_state.key();
+    init_pod_milestones(
+        pod_state,
+        pod_key,
+        ctx.accounts.pod_milestones.as_deref_mut().map(|milestones| &mut **milestones),
+        &input.milestones,
+    )?;
+
+    fund_quote_vault(
+        pod_state,
+        ctx.accounts.quote_vault.as_ref(),
+        creator.clone(),
+        system_program.clone(),
+    )?;
 
     //handler wrap sol
-    if (creator_quote_ata.mint.to_string() == NATIVE_MINT_STR) {
+    require!(
+        check_balance_on_pod_creator(creator_quote_ata, fees.creation_fee),
+        MemepodError::InsufficientFund
+    );
+    if (is_native_mint(&creator_quote_ata.mint)) {
         sync_native_amount(
             creator.clone(),
             creator_quote_ata,
-            main_state.creation_fee,
+            fees.creation_fee,
             system_program.clone(),
             token_program.clone(),
         )?;
     }
 
     //transfer
-    let base_transfer_cpi_accounts = Transfer {
+    let base_transfer_amount = input.base_amount + input.liquidity_base_amount;
+    let base_transfer_cpi_accounts = TransferChecked {
         from: ctx.accounts.creator_base_ata.to_account_info(),
+        mint: base_mint.to_account_info(),
         to: ctx.accounts.reserver_base_ata.to_account_info(),
         authority: creator.clone(),
     };
-
-    token::transfer(
-        CpiContext::new(token_program.to_account_info(), base_transfer_cpi_accounts),
-        input.base_amount,
+    token_interface::transfer_checked(
+        CpiContext::new(base_token_program.clone(), base_transfer_cpi_accounts),
+        base_transfer_amount,
+        base_mint.decimals,
     )?;
-    
+
+    // transfer fees withheld on the way in come out of the sale inventory
+    let transfer_fee =
+        base_transfer_amount - amount_after_transfer_fee(base_mint, base_transfer_amount)?;
+    require!(
+        transfer_fee < pod_state.base_amount,
+        MemepodError::InsufficientFund
+    );
+    pod_state.base_amount -= transfer_fee;
+
+    // locking team tokens in the vesting vault
+    if input.creator_vesting.is_some() {
+        let vesting_vault = ctx
+            .accounts
+            .creator_vesting_vault
+            .as_ref()
+            .ok_or(MemepodError::InvalidVestingSchedule)?;
+        let vesting_transfer_cpi_accounts = TransferChecked {
+            from: ctx.accounts.creator_base_ata.to_account_info(),
+            mint: base_mint.to_account_info(),
+            to: vesting_vault.to_account_info(),
+            authority: creator.clone(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new(base_token_program.clone(), vesting_transfer_cpi_accounts),
+            input.creator_vesting_amount(),
+            base_mint.decimals,
+        )?;
+        if let Some(vesting) = pod_state.creator_vesting.as_mut() {
+            vesting.amount = amount_after_transfer_fee(base_mint, vesting.amount)?;
+        }
+    }
+
     let quote_transfer_cpi_accounts = Transfer {
         from: ctx.accounts.creator_quote_ata.to_account_info(),
         to: ctx.accounts.fee_quote_ata.to_account_info(),
@@ -93,17 +366,10 @@ pub fn create_pod(ctx: Context<ACreatePod>, input: CreatePodInput) -> Result<()>
     };
     token::transfer(
         CpiContext::new(token_program.to_account_info(), quote_transfer_cpi_accounts),
-        main_state.creation_fee,
+        fees.creation_fee,
     )?;
 
-    emit!(CreateEvent {
-        creator: pod_state.owner,
-        base_mint: pod_state.base_mint,
-        base_amount: pod_state.base_amount,
-        token_price: pod_state.token_price,
-        expire_time: pod_state.expire_time,
-        timestamp: Clock::get()?.unix_timestamp
-    });
+    emit_create_event(pod_state, now);
 
     Ok(())
 }
@@ -120,6 +386,33 @@ pub struct ACreatePod<'info> {
         bump,
     )]
     pub main_state: Box<Account<'info, MainState>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [ProtocolStats::PREFIX_SEED],
+        bump,
+        space = 8 + ProtocolStats::MAX_SIZE
+    )]
+    pub protocol_stats: Box<Account<'info, ProtocolStats>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [
+            PodRegistry::PREFIX_SEED,
+            protocol_stats.registry_page().to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + PodRegistry::MAX_SIZE
+    )]
+    pub pod_registry: Box<Account<'info, PodRegistry>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [CreatorState::PREFIX_SEED, creator.key().as_ref()],
+        bump,
+        space = 8 + CreatorState::MAX_SIZE
+    )]
+    pub creator_state: Box<Account<'info, CreatorState>>,
     #[account(
         init,
         payer = creator,
@@ -127,31 +420,37 @@ pub struct ACreatePod<'info> {
             PodState::PREFIX_SEED,
             base_mint.key().as_ref(),
             quote_mint.key().as_ref(),
-            creator.key().as_ref()
+            creator.key().as_ref(),
+            creator_state.pod_count.to_le_bytes().as_ref(),
         ],
         bump,
         space = 16 + PodState::MAX_SIZE
     )]
     pub pod_state: Box<Account<'info, PodState>>,
 
-    pub base_mint: Box<Account<'info, Mint>>,
-    #[account(constraint = quote_mint.key().to_string() == NATIVE_MINT_STR @ MemepodError::UnknownToken)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
     pub quote_mint: Box<Account<'info, Mint>>,
+    // required for every quote mint other than wrapped SOL
+    #[account(
+        seeds = [QuoteMintConfig::PREFIX_SEED, quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub quote_config: Option<Box<Account<'info, QuoteMintConfig>>>,
 
     #[account(
         init_if_needed,
         payer=creator,
         associated_token::mint =base_mint,
         associated_token::authority = creator,
-        constraint = check_balance_on_pod_creator(creator_base_ata.as_ref(), input.base_amount) @ MemepodError::InsufficientFund
+        associated_token::token_program = base_token_program,
+        constraint = creator_base_ata.amount >= input.base_amount + input.liquidity_base_amount + input.creator_vesting_amount() @ MemepodError::InsufficientFund
     )]
-    pub creator_base_ata: Box<Account<'info, TokenAccount>>,
+    pub creator_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     #[account(
         init_if_needed,
         payer=creator,
         associated_token::mint =quote_mint,
         associated_token::authority = creator,
-        constraint = check_balance_on_pod_creator(creator_quote_ata.as_ref(), main_state.creation_fee) @ MemepodError::InsufficientFund
     )]
     pub creator_quote_ata: Box<Account<'info, TokenAccount>>,
 
@@ -173,10 +472,38 @@ pub struct ACreatePod<'info> {
         payer=creator,
         associated_token::mint = base_mint,
         associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [PodState::VESTING_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = pod_state,
+        token::token_program = base_token_program,
+    )]
+    pub creator_vesting_vault: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+    // only passed when `input.milestones` is not empty
+    #[account(
+        init,
+        payer = creator,
+        seeds = [PodMilestones::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+        space = 8 + PodMilestones::MAX_SIZE
+    )]
+    pub pod_milestones: Option<Box<Account<'info, PodMilestones>>>,
+    // lamport vault, only passed for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
     )]
-    pub reserver_base_ata: Box<Account<'info, TokenAccount>>,
+    pub quote_vault: Option<SystemAccount<'info>>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
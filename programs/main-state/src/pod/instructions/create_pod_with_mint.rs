@@ -0,0 +1,357 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata},
+    token::{
+        self, spl_token::instruction::AuthorityType, Mint, MintTo, SetAuthority, Token,
+        TokenAccount, Transfer,
+    },
+};
+use crate::{
+    error::MemepodError,
+    utils::{check_balance_on_pod_creator, is_native_mint, sync_native_amount},
+    CreatePodInput, CreatorState, MainState, PodMilestones, PodRegistry, PodState, ProtocolStats,
+    QuoteMintConfig,
+};
+
+use super::create_pod::{
+    emit_create_event, fund_quote_vault, init_pod_milestones, init_pod_state, PodMints,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+// team tokens are only minted through `CreatePodInput::creator_vesting`, so they stay locked
+pub struct CreateMintInput {
+    pub uri: String,
+}
+
+const MAX_URI_LEN: usize = 200;
+
+pub fn create_pod_with_mint(
+    ctx: Context<ACreatePodWithMint>,
+    input: CreatePodInput,
+    mint_input: CreateMintInput,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_CREATE)?;
+    let fees = main_state.fee_settings(
+        &ctx.accounts.quote_mint.key(),
+        ctx.accounts.quote_config.as_deref().map(|config| &**config),
+    )?;
+    require!(
+        mint_input.uri.len() <= MAX_URI_LEN,
+        MemepodError::UriTooLong
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let pod_state = &mut ctx.accounts.pod_state;
+    let creator = ctx.accounts.creator.to_account_info();
+    let base_mint = ctx.accounts.base_mint.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let creator_quote_ata = &ctx.accounts.creator_quote_ata;
+
+    init_pod_state(
+        pod_state,
+        creator.key(),
+        PodMints {
+            base_mint: base_mint.key(),
+            quote_mint: creator_quote_ata.mint,
+            decimal: input.token_decimal,
+            safety_flags: PodState::MINT_AUTHORITY_REVOKED
+                | PodState::FREEZE_AUTHORITY_REVOKED
+                | PodState::PROGRAM_CREATED_MINT,
+        },
+        &input,
+        now,
+    )?;
+    pod_state.index = ctx.accounts.creator_state.next_index(creator.key());
+    pod_state.pod_id = ctx
+        .accounts
+        .protocol_stats
+        .register_pod(&mut ctx.accounts.pod_registry, pod_state.key())?;
+    let pod_key = pod_state.key();
+    init_pod_milestones(
+        pod_state,
+        pod_key,
+        ctx.accounts.pod_milestones.as_deref_mut().map(|milestones| &mut **milestones),
+        &input.milestones,
+    )?;
+
+    // the pod is the mint authority until the whole supply is minted
+    let pod_signer_seeds: &[&[u8]] = &[
+        PodState::PREFIX_SEED,
+        pod_state.base_mint.as_ref(),
+        pod_state.quote_mint.as_ref(),
+        pod_state.owner.as_ref(),
+        &pod_state.index.to_le_bytes(),
+        &[ctx.bumps.pod_state],
+    ];
+    let pod_authority = pod_state.to_account_info();
+
+    // mint the whole supply up front: pod inventory, liquidity reserve and vesting
+    let reserve_mint_cpi_accounts = MintTo {
+        mint: base_mint.clone(),
+        to: ctx.accounts.reserver_base_ata.to_account_info(),
+        authority: pod_authority.clone(),
+    };
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            reserve_mint_cpi_accounts,
+            &[pod_signer_seeds],
+        ),
+        input.base_amount + input.liquidity_base_amount,
+    )?;
+
+    // team tokens go straight into the vesting vault
+    if input.creator_vesting.is_some() {
+        let vesting_vault = ctx
+            .accounts
+            .creator_vesting_vault
+            .as_ref()
+            .ok_or(MemepodError::InvalidVestingSchedule)?;
+        let vesting_mint_cpi_accounts = MintTo {
+            mint: base_mint.clone(),
+            to: vesting_vault.to_account_info(),
+            authority: pod_authority.clone(),
+        };
+        token::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                vesting_mint_cpi_accounts,
+                &[pod_signer_seeds],
+            ),
+            input.creator_vesting_amount(),
+        )?;
+    }
+
+    let metadata_cpi_accounts = CreateMetadataAccountsV3 {
+        metadata: ctx.accounts.metadata.to_account_info(),
+        mint: base_mint.clone(),
+        mint_authority: pod_authority.clone(),
+        payer: creator.clone(),
+        // the program never updates metadata, so keeping it with the pod locks it
+        update_authority: pod_authority.clone(),
+        system_program: system_program.clone(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+    metadata::create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            metadata_cpi_accounts,
+            &[pod_signer_seeds],
+        ),
+        DataV2 {
+            name: input.token_name.clone(),
+            symbol: input.token_symbol.clone(),
+            uri: mint_input.uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        false,
+        true,
+        None,
+    )?;
+
+    // supply is final from here on
+    let revoke_cpi_accounts = SetAuthority {
+        current_authority: pod_authority,
+        account_or_mint: base_mint.clone(),
+    };
+    token::set_authority(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            revoke_cpi_accounts,
+            &[pod_signer_seeds],
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    fund_quote_vault(
+        pod_state,
+        ctx.accounts.quote_vault.as_ref(),
+        creator.clone(),
+        system_program.clone(),
+    )?;
+
+    //handler wrap sol
+    require!(
+        check_balance_on_pod_creator(creator_quote_ata, fees.creation_fee),
+        MemepodError::InsufficientFund
+    );
+    if (is_native_mint(&creator_quote_ata.mint)) {
+        sync_native_amount(
+            creator.clone(),
+            creator_quote_ata,
+            fees.creation_fee,
+            system_program.clone(),
+            token_program.clone(),
+        )?;
+    }
+
+    let quote_transfer_cpi_accounts = Transfer {
+        from: ctx.accounts.creator_quote_ata.to_account_info(),
+        to: ctx.accounts.fee_quote_ata.to_account_info(),
+        authority: creator.clone(),
+    };
+    token::transfer(
+        CpiContext::new(token_program.to_account_info(), quote_transfer_cpi_accounts),
+        fees.creation_fee,
+    )?;
+
+    emit_create_event(pod_state, now);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(input: CreatePodInput)]
+pub struct ACreatePodWithMint<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [ProtocolStats::PREFIX_SEED],
+        bump,
+        space = 8 + ProtocolStats::MAX_SIZE
+    )]
+    pub protocol_stats: Box<Account<'info, ProtocolStats>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [
+            PodRegistry::PREFIX_SEED,
+            protocol_stats.registry_page().to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + PodRegistry::MAX_SIZE
+    )]
+    pub pod_registry: Box<Account<'info, PodRegistry>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [CreatorState::PREFIX_SEED, creator.key().as_ref()],
+        bump,
+        space = 8 + CreatorState::MAX_SIZE
+    )]
+    pub creator_state: Box<Account<'info, CreatorState>>,
+    #[account(
+        init,
+        payer = creator,
+        seeds =[
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            creator_state.pod_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 16 + PodState::MAX_SIZE
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    // no freeze authority, mint authority is revoked once the supply is minted
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = input.token_decimal,
+        mint::authority = pod_state,
+    )]
+    pub base_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<Account<'info, Mint>>,
+    // required for every quote mint other than wrapped SOL
+    #[account(
+        seeds = [QuoteMintConfig::PREFIX_SEED, quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub quote_config: Option<Box<Account<'info, QuoteMintConfig>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            base_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    /// CHECK: created by the token metadata program
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = main_state.fee_recipient,)]
+    /// CHECK: this should be set by admin
+    pub fee_recipient: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_mint,
+        associated_token::authority = fee_recipient,
+    )]
+    /// CHECK: this should be set by fee_recipient
+    pub fee_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = base_mint,
+        associated_token::authority = pod_state,
+        associated_token::token_program = token_program,
+    )]
+    pub reserver_base_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [PodState::VESTING_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = pod_state,
+        token::token_program = token_program,
+    )]
+    pub creator_vesting_vault: Option<Box<Account<'info, TokenAccount>>>,
+    // only passed when `input.milestones` is not empty
+    #[account(
+        init,
+        payer = creator,
+        seeds = [PodMilestones::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+        space = 8 + PodMilestones::MAX_SIZE
+    )]
+    pub pod_milestones: Option<Box<Account<'info, PodMilestones>>>,
+    // lamport vault, only passed for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub quote_vault: Option<SystemAccount<'info>>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -1,15 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
-use crate::{error::MemepodError, MainState, PodState, utils::{check_balance_on_pod_creator}};
+use crate::{error::MemepodError, MainState, PodCurve, PodState, utils::amount_after_transfer_fee};
 use std::str::FromStr;
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct EditPodInput {
     pub token_price: u64,
-    pub base_amount: u64
+    pub base_amount: u64,
+    pub curve: Option<PodCurve>,
 }
 
 pub fn edit_pod(ctx: Context<AEditPodState>, input: EditPodInput) -> Result<()> {
@@ -20,6 +21,7 @@ pub fn edit_pod(ctx: Context<AEditPodState>, input: EditPodInput) -> Result<()>
         main_state.initialized.eq(&true),
         MemepodError::Uninitialized
     );
+    main_state.require_not_paused(MainState::PAUSE_MANAGE)?;
     let pod_state = &mut ctx.accounts.pod_state;
 
     require!(
@@ -27,24 +29,38 @@ pub fn edit_pod(ctx: Context<AEditPodState>, input: EditPodInput) -> Result<()>
         MemepodError::Unauthorised
     );
 
-    require!(pod_state.is_active.eq(&true), MemepodError::NotActive);
+    require!(pod_state.is_open(), MemepodError::NotActive);
+    // repricing after the first buy would let the creator move the curve under existing buyers
+    let reprices = input.token_price != pod_state.token_price
+        || input.curve.is_some_and(|curve| curve != pod_state.curve);
+    require!(
+        !reprices || pod_state.bought_amount == 0,
+        MemepodError::PriceLocked
+    );
 
-    let token_program = ctx.accounts.token_program.to_account_info();
+    let base_token_program = ctx.accounts.base_token_program.to_account_info();
 
     //transfer
-    let base_transfer_cpi_accounts = Transfer {
+    let base_transfer_cpi_accounts = TransferChecked {
         from: ctx.accounts.creator_base_ata.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
         to: ctx.accounts.reserver_base_ata.to_account_info(),
         authority: admin.clone(),
     };
 
-    token::transfer(
-        CpiContext::new(token_program.to_account_info(), base_transfer_cpi_accounts),
+    token_interface::transfer_checked(
+        CpiContext::new(base_token_program.to_account_info(), base_transfer_cpi_accounts),
         input.base_amount,
+        ctx.accounts.base_mint.decimals,
     )?;
 
     pod_state.token_price = input.token_price;
-    pod_state.base_amount += input.base_amount;
+    pod_state.base_amount +=
+        amount_after_transfer_fee(&ctx.accounts.base_mint, input.base_amount)?;
+    if let Some(curve) = input.curve {
+        pod_state.curve = curve;
+    }
+    pod_state.curve.validate(pod_state.token_price, pod_state.base_amount)?;
 
     Ok(())
 }
@@ -68,35 +84,38 @@ pub struct AEditPodState<'info> {
             base_mint.key().as_ref(), 
             quote_mint.key().as_ref(),
             admin.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
         ],
         bump,
     )]
     pub pod_state: Box<Account<'info, PodState>>,
 
     #[account(mut,)]
-    pub base_mint: Box<Account<'info, Mint>>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
     
     #[account(mut,)]
-    pub quote_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         init_if_needed,
         payer=admin,
         associated_token::mint =base_mint,
         associated_token::authority = admin,
-        constraint = check_balance_on_pod_creator(creator_base_ata.as_ref(), input.base_amount) @ MemepodError::InsufficientFund
+        associated_token::token_program = base_token_program,
+        constraint = creator_base_ata.amount >= input.base_amount @ MemepodError::InsufficientFund
     )]
-    pub creator_base_ata: Box<Account<'info, TokenAccount>>,
+    pub creator_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer=admin,
         associated_token::mint = base_mint,
         associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
     )]
-    pub reserver_base_ata: Box<Account<'info, TokenAccount>>,
+    pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::{error::MemepodError, FinalizeEvent, MainState, PodState, PodStatus, UnsoldPolicy};
+
+pub fn finalize_pod(ctx: Context<AFinalizePod>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_FINALIZE)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    require!(pod_state.is_open(), MemepodError::NotActive);
+    require!(
+        !pod_state.is_graduation_due(),
+        MemepodError::PodMustGraduate
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(!pod_state.is_paused(now), MemepodError::PodPaused);
+    pod_state.resume_if_due(now)?;
+    require!(pod_state.is_expired(now), MemepodError::PodNotExpired);
+    pod_state.start_if_due(now)?;
+
+    if pod_state.is_soft_cap_reached() {
+        pod_state.transition(PodStatus::Succeeded)?;
+        let proceeds_amount = pod_state.raised_amount;
+        pod_state.start_release(proceeds_amount, now);
+    } else {
+        pod_state.transition(PodStatus::Failed)?;
+    }
+
+    let base_token_program = ctx.accounts.base_token_program.to_account_info();
+    // liquidity reserve goes with the unsold inventory when the pod does not graduate
+    let unsold_amount =
+        pod_state.base_amount - pod_state.bought_amount + pod_state.liquidity_base_amount;
+    pod_state.liquidity_base_amount = 0;
+    let signer_seeds: &[&[u8]] = &[
+        PodState::PREFIX_SEED,
+        pod_state.base_mint.as_ref(),
+        pod_state.quote_mint.as_ref(),
+        pod_state.owner.as_ref(),
+        &pod_state.index.to_le_bytes(),
+        &[ctx.bumps.pod_state],
+    ];
+
+    if unsold_amount > 0 {
+        match pod_state.unsold_policy {
+            UnsoldPolicy::Burn => {
+                let cpi_accounts = Burn {
+                    mint: ctx.accounts.base_mint.to_account_info(),
+                    from: ctx.accounts.reserver_base_ata.to_account_info(),
+                    authority: pod_state.to_account_info(),
+                };
+                token_interface::burn(
+                    CpiContext::new_with_signer(base_token_program, cpi_accounts, &[signer_seeds]),
+                    unsold_amount,
+                )?;
+            }
+            UnsoldPolicy::ReturnToCreator => {
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.reserver_base_ata.to_account_info(),
+                    mint: ctx.accounts.base_mint.to_account_info(),
+                    to: ctx.accounts.creator_base_ata.to_account_info(),
+                    authority: pod_state.to_account_info(),
+                };
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(base_token_program, cpi_accounts, &[signer_seeds]),
+                    unsold_amount,
+                    ctx.accounts.base_mint.decimals,
+                )?;
+            }
+        }
+    }
+
+    pod_state.base_amount = pod_state.bought_amount;
+
+    emit!(FinalizeEvent {
+        creator: pod_state.owner,
+        base_mint: pod_state.base_mint,
+        bought_amount: pod_state.bought_amount,
+        unsold_amount,
+        unsold_policy: pod_state.unsold_policy,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AFinalizePod<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub creator: SystemAccount<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(mut, address = pod_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = pod_state.quote_mint)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = base_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = base_token_program,
+    )]
+    pub creator_base_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,9 +1,15 @@
 pub mod create_pod;
 pub use create_pod::*;
 
+pub mod create_pod_with_mint;
+pub use create_pod_with_mint::*;
+
 pub mod close_pod;
 pub use close_pod::*;
 
+pub mod withdraw_legacy_pod;
+pub use withdraw_legacy_pod::*;
+
 pub mod edit_pod;
 pub use edit_pod::*;
 
@@ -12,3 +18,36 @@ pub use buy::*;
 
 pub mod withdraw;
 pub use withdraw::*;
+
+pub mod sell;
+pub use sell::*;
+
+pub mod finalize_pod;
+pub use finalize_pod::*;
+
+pub mod refund;
+pub use refund::*;
+
+pub mod close_position;
+pub use close_position::*;
+
+pub mod vote_milestone;
+pub use vote_milestone::*;
+
+pub mod resolve_milestone;
+pub use resolve_milestone::*;
+
+pub mod reclaim_milestone_funds;
+pub use reclaim_milestone_funds::*;
+
+pub mod claim;
+pub use claim::*;
+
+pub mod claim_creator_vesting;
+pub use claim_creator_vesting::*;
+
+pub mod reschedule_pod;
+pub use reschedule_pod::*;
+
+pub mod pause_pod;
+pub use pause_pod::*;
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::{error::MemepodError, MainState, PauseEvent, PodState, ResumeEvent};
+
+pub fn pause_pod(ctx: Context<APausePod>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_MANAGE)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(!pod_state.is_expired(now), MemepodError::PodExpired);
+    pod_state.pause(now)?;
+
+    emit!(PauseEvent {
+        creator: pod_state.owner,
+        base_mint: pod_state.base_mint,
+        resume_deadline: pod_state.pause_end(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn resume_pod(ctx: Context<APausePod>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_MANAGE)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    let now = Clock::get()?.unix_timestamp;
+    let paused_duration = pod_state.resume(now)?;
+
+    emit!(ResumeEvent {
+        creator: pod_state.owner,
+        base_mint: pod_state.base_mint,
+        paused_duration,
+        expire_time: pod_state.expire_time,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct APausePod<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            pod_state.base_mint.as_ref(),
+            pod_state.quote_mint.as_ref(),
+            admin.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+}
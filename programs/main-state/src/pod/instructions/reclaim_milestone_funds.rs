@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface,
+};
+use crate::{
+    error::MemepodError,
+    utils::{transfer_lamports, unwrap_native_account},
+    MainState, MilestoneReclaimEvent, PodMilestones, PodState, UserPosition,
+};
+
+pub fn reclaim_milestone_funds(ctx: Context<AReclaimMilestoneFunds>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_REFUND)?;
+
+    let pod_milestones = &ctx.accounts.pod_milestones;
+    require!(pod_milestones.failed, MemepodError::MilestoneNotFailed);
+    let user_position = &mut ctx.accounts.user_position;
+    require!(
+        !user_position.milestone_reclaimed,
+        MemepodError::AlreadyReclaimed
+    );
+
+    let pod_state = &ctx.accounts.pod_state;
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    // withheld tranches are shared out by the tokens each buyer still holds from the pod
+    let quote_amount = (pod_milestones.reclaim_amount as u128)
+        .checked_mul(user_position.remaining_base() as u128)
+        .unwrap()
+        .checked_div(pod_milestones.reclaim_weight as u128)
+        .ok_or(MemepodError::InsufficientFund)? as u64;
+    require!(quote_amount > 0, MemepodError::InsufficientFund);
+    user_position.milestone_reclaimed = true;
+
+    if pod_state.native_quote {
+        // native pods pay lamports straight from the quote vault
+        let quote_vault = ctx
+            .accounts
+            .quote_vault
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let pod_key = pod_state.key();
+        transfer_lamports(
+            quote_vault.to_account_info(),
+            buyer.clone(),
+            quote_amount,
+            ctx.accounts.system_program.to_account_info(),
+            &[&[
+                PodState::QUOTE_VAULT_SEED,
+                pod_key.as_ref(),
+                &[ctx.bumps.quote_vault],
+            ]],
+        )?;
+    } else {
+        let buyer_quote_ata = ctx
+            .accounts
+            .buyer_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let reserver_quote_ata = ctx
+            .accounts
+            .reserver_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+
+        // sending reclaimed amount (sol)
+        let quote_transfer_cpi_account = Transfer {
+            from: reserver_quote_ata.to_account_info(),
+            to: buyer_quote_ata.to_account_info(),
+            authority: pod_state.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                quote_transfer_cpi_account,
+                &[&[
+                    PodState::PREFIX_SEED,
+                    pod_state.base_mint.as_ref(),
+                    pod_state.quote_mint.as_ref(),
+                    pod_state.owner.as_ref(),
+                    &pod_state.index.to_le_bytes(),
+                    &[ctx.bumps.pod_state],
+                ]],
+            ),
+            quote_amount,
+        )?;
+
+        // unwrap sol
+        unwrap_native_account(buyer.clone(), buyer_quote_ata, token_program)?;
+    }
+
+    emit!(MilestoneReclaimEvent {
+        pod: pod_state.key(),
+        user: buyer.key(),
+        quote_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AReclaimMilestoneFunds<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub creator: SystemAccount<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(
+        seeds = [PodMilestones::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub pod_milestones: Box<Account<'info, PodMilestones>>,
+
+    #[account(
+        mut,
+        seeds = [
+            UserPosition::PREFIX_SEED,
+            pod_state.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(address = pod_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(address = pod_state.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = pod_state,
+    )]
+    pub reserver_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    // lamport vault, passed instead of the quote token accounts for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub quote_vault: Option<SystemAccount<'info>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
+};
+use crate::{
+    error::MemepodError,
+    utils::{amount_after_transfer_fee, transfer_lamports, unwrap_native_account},
+    MainState, PodState, RefundEvent, UserPosition,
+};
+
+pub fn refund(ctx: Context<ARefund>, base_amount: u64) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_REFUND)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(pod_state.is_refundable(now), MemepodError::NotRefundable);
+    let user_position = &mut ctx.accounts.user_position;
+    require!(
+        base_amount > 0 && base_amount <= user_position.remaining_base(),
+        MemepodError::InsufficientFund
+    );
+
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    // buyers get back what they put in, pro-rata to the tokens they hand back
+    let quote_amount = ((base_amount as u128)
+        .checked_mul(user_position.remaining_contribution() as u128)
+        .unwrap()
+        .checked_div(user_position.remaining_base() as u128)
+        .unwrap() as u64)
+        .min(pod_state.raised_amount);
+    user_position.record_return(quote_amount, base_amount);
+    pod_state.held_base_amount -= base_amount;
+    if pod_state.is_claim_mode() {
+        pod_state.bought_amount -= base_amount;
+    } else {
+        pod_state.bought_amount -=
+            amount_after_transfer_fee(&ctx.accounts.base_mint, base_amount)?;
+    }
+    pod_state.raised_amount -= quote_amount;
+
+    // sending tokens back to reserve ata (meme), unclaimed allocations never left it
+    if !pod_state.is_claim_mode() {
+        let base_transfer_cpi_account = TransferChecked {
+            from: ctx.accounts.buyer_base_ata.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
+            to: ctx.accounts.reserver_base_ata.to_account_info(),
+            authority: buyer.clone(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.base_token_program.to_account_info(),
+                base_transfer_cpi_account,
+            ),
+            base_amount,
+            ctx.accounts.base_mint.decimals,
+        )?;
+    }
+
+    if pod_state.native_quote {
+        // native pods refund lamports straight from the quote vault
+        let quote_vault = ctx
+            .accounts
+            .quote_vault
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let pod_key = pod_state.key();
+        transfer_lamports(
+            quote_vault.to_account_info(),
+            buyer.clone(),
+            quote_amount,
+            ctx.accounts.system_program.to_account_info(),
+            &[&[
+                PodState::QUOTE_VAULT_SEED,
+                pod_key.as_ref(),
+                &[ctx.bumps.quote_vault],
+            ]],
+        )?;
+    } else {
+        let buyer_quote_ata = ctx
+            .accounts
+            .buyer_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let reserver_quote_ata = ctx
+            .accounts
+            .reserver_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+
+        // sending refunded amount (sol)
+        let quote_transfer_cpi_account = Transfer {
+            from: reserver_quote_ata.to_account_info(),
+            to: buyer_quote_ata.to_account_info(),
+            authority: pod_state.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                quote_transfer_cpi_account,
+                &[&[
+                    PodState::PREFIX_SEED,
+                    pod_state.base_mint.as_ref(),
+                    pod_state.quote_mint.as_ref(),
+                    pod_state.owner.as_ref(),
+                    &pod_state.index.to_le_bytes(),
+                    &[ctx.bumps.pod_state],
+                ]],
+            ),
+            quote_amount,
+        )?;
+
+        // unwrap sol
+        unwrap_native_account(buyer.clone(), buyer_quote_ata, token_program)?;
+    }
+
+    emit!(RefundEvent {
+        user: buyer.key(),
+        base_mint: pod_state.base_mint,
+        base_amount,
+        quote_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(base_amount: u64)]
+pub struct ARefund<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub creator: SystemAccount<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            UserPosition::PREFIX_SEED,
+            pod_state.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(address = pod_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(address = pod_state.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = base_token_program,
+        constraint = pod_state.is_claim_mode() || buyer_base_ata.amount >= base_amount @ MemepodError::InsufficientFund
+    )]
+    pub buyer_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = pod_state,
+    )]
+    pub reserver_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    // lamport vault, passed instead of the quote token accounts for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub quote_vault: Option<SystemAccount<'info>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::{error::MemepodError, MainState, PodMilestones, PodState, PodStatus, RescheduleEvent};
+
+pub fn reschedule_pod(
+    ctx: Context<AReschedulePod>,
+    start_time: u64,
+    expire_time: Option<u64>,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_MANAGE)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(pod_state.is_open(), MemepodError::NotActive);
+    // only a launch that has not happened yet can move
+    require!(
+        pod_state.status == PodStatus::Scheduled && !pod_state.is_started(now),
+        MemepodError::PodAlreadyStarted
+    );
+    require!(start_time > now as u64, MemepodError::InvalidStartTime);
+
+    let expire_time = expire_time.unwrap_or(pod_state.expire_time);
+    require!(expire_time > start_time, MemepodError::InvalidExpireTime);
+    let pod_milestones = &ctx.accounts.pod_milestones;
+    if !pod_milestones.data_is_empty() {
+        require_keys_eq!(*pod_milestones.owner, crate::ID);
+        let pod_milestones =
+            PodMilestones::try_deserialize(&mut &pod_milestones.try_borrow_data()?[..])?;
+        require!(
+            pod_milestones.fit_expire_time(expire_time),
+            MemepodError::InvalidMilestones
+        );
+    }
+    pod_state.start_time = start_time;
+    pod_state.expire_time = expire_time;
+
+    emit!(RescheduleEvent {
+        creator: pod_state.owner,
+        base_mint: pod_state.base_mint,
+        start_time,
+        expire_time,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AReschedulePod<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            pod_state.base_mint.as_ref(),
+            pod_state.quote_mint.as_ref(),
+            admin.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+    /// CHECK: empty when the pod has no milestones, loaded as PodMilestones otherwise
+    #[account(
+        seeds = [PodMilestones::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub pod_milestones: UncheckedAccount<'info>,
+}
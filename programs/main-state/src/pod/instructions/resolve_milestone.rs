@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::MemepodError, MainState, MilestoneResolvedEvent, MilestoneStatus, PodMilestones,
+    PodState,
+};
+
+pub fn resolve_milestone(ctx: Context<AResolveMilestone>, index: u8) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_MILESTONES)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    require!(pod_state.is_succeeded(), MemepodError::PodNotFinished);
+
+    let pod_milestones = &mut ctx.accounts.pod_milestones;
+    require!(index < pod_milestones.count, MemepodError::InvalidMilestones);
+    require!(!pod_milestones.failed, MemepodError::MilestoneClosed);
+
+    let now = Clock::get()?.unix_timestamp;
+    let milestone = &mut pod_milestones.milestones[index as usize];
+    require!(
+        milestone.status == MilestoneStatus::Pending,
+        MemepodError::MilestoneClosed
+    );
+
+    // a tranche needs a majority of all tokens held by the pod's buyers
+    let remaining = pod_state.milestone_amount - pod_state.milestone_released_amount;
+    let mut released_amount = 0;
+    if (milestone.votes_for as u128) * 2 > pod_state.held_base_amount as u128 {
+        milestone.status = MilestoneStatus::Released;
+        released_amount = milestone.amount.min(remaining);
+        pod_state.milestone_released_amount += released_amount;
+    } else {
+        require!(now >= milestone.deadline, MemepodError::MilestoneVoteOpen);
+        milestone.status = MilestoneStatus::Rejected;
+
+        // whatever is still held back goes back to buyers
+        pod_milestones.failed = true;
+        pod_milestones.reclaim_amount = remaining;
+        pod_milestones.reclaim_weight = pod_state.held_base_amount;
+        pod_state.milestone_amount = pod_state.milestone_released_amount;
+    }
+
+    emit!(MilestoneResolvedEvent {
+        pod: pod_state.key(),
+        index,
+        approved: released_amount > 0,
+        released_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AResolveMilestone<'info> {
+    pub cranker: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(mut)]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(
+        mut,
+        seeds = [PodMilestones::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub pod_milestones: Box<Account<'info, PodMilestones>>,
+}
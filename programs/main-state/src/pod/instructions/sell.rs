@@ -0,0 +1,344 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
+};
+use crate::{
+    error::MemepodError,
+    utils::{
+        amount_after_transfer_fee, calculate_trading_fee, split_trading_fee,
+        transfer_lamports, unwrap_native_account,
+    },
+    MainState, PodState, PodStatus, QuoteMintConfig, SellEvent, UserPosition,
+};
+
+pub fn sell(ctx: Context<ASell>, base_amount: u64, min_quote_out: u64) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_SELL)?;
+    let fees = main_state.fee_settings(
+        &ctx.accounts.pod_state.quote_mint,
+        ctx.accounts.quote_config.as_deref().map(|config| &**config),
+    )?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        matches!(
+            pod_state.effective_status(now),
+            PodStatus::Live | PodStatus::SoldOut
+        ),
+        MemepodError::NotActive
+    );
+    require!(!pod_state.is_expired(now), MemepodError::PodExpired);
+    // a pause that ran out by itself ends here, extending the expiry
+    pod_state.resume_if_due(now)?;
+    require!(
+        base_amount > 0 && base_amount <= pod_state.bought_amount,
+        MemepodError::InsufficientFund
+    );
+
+    let seller = ctx.accounts.seller.to_account_info();
+    let seller_base_ata = &ctx.accounts.seller_base_ata;
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    // only what reaches the reserve after transfer fees goes back on the curve
+    let returned_amount = if pod_state.is_claim_mode() {
+        base_amount
+    } else {
+        amount_after_transfer_fee(&ctx.accounts.base_mint, base_amount)?
+    };
+    let quote_amount = pod_state.compute_payable_amount_on_sell(returned_amount)?;
+    pod_state.raised_amount = pod_state.raised_amount.saturating_sub(quote_amount);
+    let fee = calculate_trading_fee(fees.trading_fee, quote_amount);
+    let (owner_fee, creator_fee) =
+        split_trading_fee(fee, fees.owner_fee, fees.creator_fee);
+    let output_amount = quote_amount - fee;
+    require!(
+        output_amount >= min_quote_out,
+        MemepodError::SlippageExceeded
+    );
+
+    // only tokens bought from this pod can be sold back into it
+    let user_position = &mut ctx.accounts.user_position;
+    require!(
+        base_amount <= user_position.remaining_base(),
+        MemepodError::InsufficientFund
+    );
+
+    // unclaimed allocation is sold straight out of the reserve
+    if !pod_state.is_claim_mode() {
+        // sending tokens back to reserve ata (meme)
+        let input_amount_transfer_cpi_account = TransferChecked {
+            from: seller_base_ata.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
+            to: ctx.accounts.reserver_base_ata.to_account_info(),
+            authority: seller.clone(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.base_token_program.to_account_info(),
+                input_amount_transfer_cpi_account,
+            ),
+            base_amount,
+            ctx.accounts.base_mint.decimals,
+        )?;
+    }
+    user_position.record_return(quote_amount, base_amount);
+    pod_state.held_base_amount -= base_amount;
+    // selling back below the target reopens the sale
+    if pod_state.status == PodStatus::SoldOut && !pod_state.is_target_reached() {
+        pod_state.transition(PodStatus::Live)?;
+    }
+
+    let system_program = ctx.accounts.system_program.to_account_info();
+    if pod_state.native_quote {
+        // native pods pay out lamports straight from the quote vault
+        let quote_vault = ctx
+            .accounts
+            .quote_vault
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        require!(
+            quote_vault.lamports() >= quote_amount + Rent::get()?.minimum_balance(0),
+            MemepodError::InsufficientFund
+        );
+        let pod_key = pod_state.key();
+        let vault_signer_seeds: &[&[u8]] = &[
+            PodState::QUOTE_VAULT_SEED,
+            pod_key.as_ref(),
+            &[ctx.bumps.quote_vault],
+        ];
+        transfer_lamports(
+            quote_vault.to_account_info(),
+            ctx.accounts.fee_recipient.to_account_info(),
+            owner_fee,
+            system_program.clone(),
+            &[vault_signer_seeds],
+        )?;
+        transfer_lamports(
+            quote_vault.to_account_info(),
+            ctx.accounts.creator.to_account_info(),
+            creator_fee,
+            system_program.clone(),
+            &[vault_signer_seeds],
+        )?;
+        transfer_lamports(
+            quote_vault.to_account_info(),
+            seller.clone(),
+            output_amount,
+            system_program,
+            &[vault_signer_seeds],
+        )?;
+    } else {
+        let seller_quote_ata = ctx
+            .accounts
+            .seller_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let fee_quote_ata = ctx
+            .accounts
+            .fee_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let creator_quote_ata = ctx
+            .accounts
+            .creator_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let reserver_quote_ata = ctx
+            .accounts
+            .reserver_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        require!(
+            reserver_quote_ata.amount >= quote_amount,
+            MemepodError::InsufficientFund
+        );
+
+        let signer_seeds: &[&[u8]] = &[
+            PodState::PREFIX_SEED,
+            pod_state.base_mint.as_ref(),
+            pod_state.quote_mint.as_ref(),
+            pod_state.owner.as_ref(),
+            &pod_state.index.to_le_bytes(),
+            &[ctx.bumps.pod_state],
+        ];
+
+        // sending owner fee
+        let fee_transfer_cpi_account = Transfer {
+            from: reserver_quote_ata.to_account_info(),
+            to: fee_quote_ata.to_account_info(),
+            authority: pod_state.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                fee_transfer_cpi_account,
+                &[signer_seeds],
+            ),
+            owner_fee,
+        )?;
+
+        // sending creator fee
+        let creator_fee_transfer_cpi_account = Transfer {
+            from: reserver_quote_ata.to_account_info(),
+            to: creator_quote_ata.to_account_info(),
+            authority: pod_state.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                creator_fee_transfer_cpi_account,
+                &[signer_seeds],
+            ),
+            creator_fee,
+        )?;
+
+        // sending output amount (sol)
+        let output_amount_transfer_cpi_account = Transfer {
+            from: reserver_quote_ata.to_account_info(),
+            to: seller_quote_ata.to_account_info(),
+            authority: pod_state.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                output_amount_transfer_cpi_account,
+                &[signer_seeds],
+            ),
+            output_amount,
+        )?;
+
+        // unwrap sol
+        unwrap_native_account(seller.clone(), seller_quote_ata, token_program)?;
+    }
+
+    emit!(SellEvent {
+        user: seller.key(),
+        base_mint: pod_state.base_mint,
+        base_amount,
+        quote_amount: output_amount,
+        owner_fee,
+        creator_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(base_amount: u64)]
+pub struct ASell<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(mut, address = main_state.fee_recipient,)]
+    /// CHECK: this should be set by admin
+    pub fee_recipient: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = quote_mint,
+        associated_token::authority = fee_recipient,
+    )]
+    /// CHECK: this should be set by fee_recipient
+    pub fee_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            UserPosition::PREFIX_SEED,
+            pod_state.key().as_ref(),
+            seller.key().as_ref()
+        ],
+        bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(address = pod_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(address = pod_state.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [QuoteMintConfig::PREFIX_SEED, quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub quote_config: Option<Box<Account<'info, QuoteMintConfig>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = base_token_program,
+        constraint = pod_state.is_claim_mode() || seller_base_ata.amount >= base_amount @ MemepodError::InsufficientFund
+    )]
+    pub seller_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = quote_mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = pod_state,
+    )]
+    pub reserver_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    // lamport vault, passed instead of the quote token accounts for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub quote_vault: Option<SystemAccount<'info>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::{
+    error::MemepodError, MainState, MilestoneStatus, MilestoneVoteEvent, PodMilestones, PodState,
+    UserPosition,
+};
+
+pub fn vote_milestone(ctx: Context<AVoteMilestone>, index: u8, approve: bool) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_MILESTONES)?;
+
+    let pod_state = &ctx.accounts.pod_state;
+    require!(pod_state.is_succeeded(), MemepodError::PodNotFinished);
+
+    let pod_milestones = &mut ctx.accounts.pod_milestones;
+    require!(index < pod_milestones.count, MemepodError::InvalidMilestones);
+    require!(!pod_milestones.failed, MemepodError::MilestoneClosed);
+
+    let now = Clock::get()?.unix_timestamp;
+    let milestone = &mut pod_milestones.milestones[index as usize];
+    require!(
+        milestone.status == MilestoneStatus::Pending && now < milestone.deadline,
+        MemepodError::MilestoneClosed
+    );
+
+    // buyers vote with the base tokens they bought in this pod, positions are frozen once the
+    // pod has succeeded so this matches the held_base_amount the vote is resolved against
+    let user_position = &mut ctx.accounts.user_position;
+    require!(
+        user_position.milestone_votes & (1 << index) == 0,
+        MemepodError::AlreadyVoted
+    );
+    let weight = user_position.remaining_base();
+    require!(weight > 0, MemepodError::InsufficientFund);
+    user_position.milestone_votes |= 1 << index;
+
+    if approve {
+        milestone.votes_for += weight;
+    } else {
+        milestone.votes_against += weight;
+    }
+
+    emit!(MilestoneVoteEvent {
+        pod: pod_state.key(),
+        voter: ctx.accounts.voter.key(),
+        index,
+        approve,
+        weight,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AVoteMilestone<'info> {
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(
+        mut,
+        seeds = [PodMilestones::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub pod_milestones: Box<Account<'info, PodMilestones>>,
+
+    #[account(
+        mut,
+        seeds = [
+            UserPosition::PREFIX_SEED,
+            pod_state.key().as_ref(),
+            voter.key().as_ref()
+        ],
+        bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+}
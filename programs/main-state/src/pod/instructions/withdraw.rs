@@ -2,8 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
+};
+use crate::{
+    error::MemepodError,
+    utils::{transfer_lamports, unwrap_native_account},
+    MainState, PodState,
 };
-use crate::{constants::NATIVE_MINT_STR, error::MemepodError, MainState, PodState};
 use std::str::FromStr;
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -20,6 +25,7 @@ pub fn withdraw(ctx: Context<AWithdrawState>, input: WithdrawInput) -> Result<()
         main_state.initialized.eq(&true),
         MemepodError::Uninitialized
     );
+    main_state.require_not_paused(MainState::PAUSE_WITHDRAW)?;
     let pod_state = &mut ctx.accounts.pod_state;
 
     require!(
@@ -27,10 +33,12 @@ pub fn withdraw(ctx: Context<AWithdrawState>, input: WithdrawInput) -> Result<()
         MemepodError::Unauthorised
     );
 
-    require!(pod_state.is_active.eq(&true), MemepodError::NotActive);
+    require!(
+        pod_state.is_open() || pod_state.is_settled(),
+        MemepodError::NotActive
+    );
 
     let admin_base_ata = ctx.accounts.admin_base_ata.to_account_info();
-    let admin_quote_ata = ctx.accounts.admin_quote_ata.to_account_info();
     let token_program = ctx.accounts.token_program.to_account_info();
 
     // send tokens in pool and virt
@@ -42,58 +50,100 @@ pub fn withdraw(ctx: Context<AWithdrawState>, input: WithdrawInput) -> Result<()
 
         pod_state.base_amount -= input.base_amount;
 
-        let pod_base_transfer_cpi_account = Transfer {
+        let pod_base_transfer_cpi_account = TransferChecked {
             from: ctx.accounts.reserver_base_ata.to_account_info(),
+            mint: ctx.accounts.base_mint.to_account_info(),
             to: admin_base_ata.clone(),
             authority: pod_state.to_account_info(),
         };
 
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                token_program.clone(),
+                ctx.accounts.base_token_program.to_account_info(),
                 pod_base_transfer_cpi_account,
                 &[&[
                     PodState::PREFIX_SEED,
                     pod_state.base_mint.as_ref(),
                     pod_state.quote_mint.as_ref(),
                     pod_state.owner.as_ref(),
+                    &pod_state.index.to_le_bytes(),
                     &[ctx.bumps.pod_state],
                 ]],
             ),
             input.base_amount,
+            ctx.accounts.base_mint.decimals,
         )?;
     }
 
-    // send SOL in pool
+    // send quote in pool
     if (input.quote_amount > 0) {
-
-        let pod_quote_transfer_cpi_account = Transfer {
-            from: ctx.accounts.reserver_quote_ata.to_account_info(),
-            to: admin_quote_ata.clone(),
-            authority: pod_state.to_account_info(),
-        };
-        token::transfer(
-            CpiContext::new_with_signer(
-                token_program.clone(),
-                pod_quote_transfer_cpi_account,
+        // proceeds stay escrowed until the pod succeeds, then follow the release schedule
+        require!(
+            pod_state.is_soft_cap_reached(),
+            MemepodError::SoftCapNotReached
+        );
+        require!(pod_state.is_succeeded(), MemepodError::ProceedsEscrowed);
+        require!(
+            input.quote_amount <= pod_state.releasable_amount(Clock::get()?.unix_timestamp),
+            MemepodError::ProceedsEscrowed
+        );
+        pod_state.released_amount += input.quote_amount;
+
+        if pod_state.native_quote {
+            // native pods release lamports straight from the quote vault
+            let quote_vault = ctx
+                .accounts
+                .quote_vault
+                .as_ref()
+                .ok_or(MemepodError::MissingQuoteAccount)?;
+            let pod_key = pod_state.key();
+            transfer_lamports(
+                quote_vault.to_account_info(),
+                admin,
+                input.quote_amount,
+                ctx.accounts.system_program.to_account_info(),
                 &[&[
-                    PodState::PREFIX_SEED,
-                    pod_state.base_mint.as_ref(),
-                    pod_state.quote_mint.as_ref(),
-                    pod_state.owner.as_ref(),
-                    &[ctx.bumps.pod_state],
+                    PodState::QUOTE_VAULT_SEED,
+                    pod_key.as_ref(),
+                    &[ctx.bumps.quote_vault],
                 ]],
-            ),
-            input.quote_amount,
-        )?;
-
-        let close_ata_cpi_accounts = CloseAccount {
-            account: admin_quote_ata.to_account_info(),
-            authority: admin.clone(),
-            destination: admin,
-        };
-
-        token::close_account(CpiContext::new(token_program, close_ata_cpi_accounts))?;
+            )?;
+        } else {
+            let admin_quote_ata = ctx
+                .accounts
+                .admin_quote_ata
+                .as_ref()
+                .ok_or(MemepodError::MissingQuoteAccount)?;
+            let reserver_quote_ata = ctx
+                .accounts
+                .reserver_quote_ata
+                .as_ref()
+                .ok_or(MemepodError::MissingQuoteAccount)?;
+
+            let pod_quote_transfer_cpi_account = Transfer {
+                from: reserver_quote_ata.to_account_info(),
+                to: admin_quote_ata.to_account_info(),
+                authority: pod_state.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    pod_quote_transfer_cpi_account,
+                    &[&[
+                        PodState::PREFIX_SEED,
+                        pod_state.base_mint.as_ref(),
+                        pod_state.quote_mint.as_ref(),
+                        pod_state.owner.as_ref(),
+                        &pod_state.index.to_le_bytes(),
+                        &[ctx.bumps.pod_state],
+                    ]],
+                ),
+                input.quote_amount,
+            )?;
+
+            // unwrap sol
+            unwrap_native_account(admin, admin_quote_ata, token_program)?;
+        }
     }
 
     Ok(())
@@ -117,13 +167,14 @@ pub struct AWithdrawState<'info> {
             base_mint.key().as_ref(), 
             quote_mint.key().as_ref(),
             admin.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
         ],
         bump,
     )]
     pub pod_state: Box<Account<'info, PodState>>,
 
     #[account(mut,)]
-    pub base_mint: Box<Account<'info, Mint>>,
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
     #[account(mut,)]
     pub quote_mint: Box<Account<'info, Mint>>,
 
@@ -132,32 +183,42 @@ pub struct AWithdrawState<'info> {
         payer = admin,
         associated_token::mint = base_mint,
         associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
     )]
-    pub reserver_base_ata: Box<Account<'info, TokenAccount>>,
+    pub reserver_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     #[account(
         init_if_needed,
         payer = admin,
         associated_token::mint = quote_mint,
         associated_token::authority = pod_state,
     )]
-    pub reserver_quote_ata: Box<Account<'info, TokenAccount>>,
+    pub reserver_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
 
     #[account(
         init_if_needed,
         payer = admin,
         associated_token::mint = base_mint,
         associated_token::authority = admin,
+        associated_token::token_program = base_token_program,
     )]
-    pub admin_base_ata: Box<Account<'info, TokenAccount>>,
+    pub admin_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     #[account(
         init_if_needed,
         payer = admin,
         associated_token::mint = quote_mint,
         associated_token::authority = admin,
     )]
-    pub admin_quote_ata: Box<Account<'info, TokenAccount>>,
+    pub admin_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    // lamport vault, passed instead of the quote token accounts for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub quote_vault: Option<SystemAccount<'info>>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
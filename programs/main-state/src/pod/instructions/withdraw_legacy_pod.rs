@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+use crate::{error::MemepodError, LegacyPodState, MainState, PodState};
+
+// drains and closes a pod created before per-creator indexes, its owner could always withdraw it
+pub fn withdraw_legacy_pod(ctx: Context<AWithdrawLegacyPod>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_WITHDRAW)?;
+
+    let owner = ctx.accounts.owner.to_account_info();
+    let legacy_pod = ctx.accounts.legacy_pod.to_account_info();
+    let legacy_state = LegacyPodState::try_from_account(&legacy_pod)?;
+    require!(legacy_state.owner == owner.key(), MemepodError::Unauthorised);
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let base_mint = ctx.accounts.base_mint.key();
+    let quote_mint = ctx.accounts.quote_mint.key();
+    let signer_seeds: &[&[u8]] = &[
+        PodState::PREFIX_SEED,
+        base_mint.as_ref(),
+        quote_mint.as_ref(),
+        legacy_state.owner.as_ref(),
+        &[ctx.bumps.legacy_pod],
+    ];
+
+    for (reserve, destination) in [
+        (&ctx.accounts.reserver_base_ata, &ctx.accounts.owner_base_ata),
+        (&ctx.accounts.reserver_quote_ata, &ctx.accounts.owner_quote_ata),
+    ] {
+        let transfer_cpi_accounts = Transfer {
+            from: reserve.to_account_info(),
+            to: destination.to_account_info(),
+            authority: legacy_pod.clone(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                transfer_cpi_accounts,
+                &[signer_seeds],
+            ),
+            reserve.amount,
+        )?;
+
+        let close_cpi_accounts = CloseAccount {
+            account: reserve.to_account_info(),
+            destination: owner.clone(),
+            authority: legacy_pod.clone(),
+        };
+        token::close_account(CpiContext::new_with_signer(
+            token_program.clone(),
+            close_cpi_accounts,
+            &[signer_seeds],
+        ))?;
+    }
+
+    // closing the pod account itself hands its rent back to the owner
+    let rent_amount = legacy_pod.lamports();
+    **legacy_pod.try_borrow_mut_lamports()? = 0;
+    **owner.try_borrow_mut_lamports()? += rent_amount;
+    legacy_pod.assign(&System::id());
+    legacy_pod.realloc(0, false)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AWithdrawLegacyPod<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    /// CHECK: pre-index layout, read by LegacyPodState in the handler
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        owner = crate::ID,
+    )]
+    pub legacy_pod: UncheckedAccount<'info>,
+
+    pub base_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = legacy_pod,
+    )]
+    pub reserver_base_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = legacy_pod,
+    )]
+    pub reserver_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = base_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_base_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = quote_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -4,5 +4,8 @@ pub use instructions::*;
 pub mod state;
 pub use state::*;
 
+pub mod curve;
+pub use curve::*;
+
 pub mod event;
 pub use event::*;
@@ -1,4 +1,7 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::MAX_PAUSE_DURATION, error::MemepodError, PodCurve};
 
 #[account]
 pub struct PodState {
@@ -6,27 +9,766 @@ pub struct PodState {
     pub token_name: [u8; 32],
     pub token_symbol: [u8; 10],
     pub decimal: u8,
+    pub safety_flags: u8,
     pub base_mint: Pubkey,
     pub quote_mint: Pubkey,
+    pub native_quote: bool, // quote held as lamports in the quote vault instead of wsol
     pub owner: Pubkey,
+    pub index: u64, // per-creator pod index, part of the PDA seeds
+    pub pod_id: u64, // protocol-wide id, see PodRegistry
     pub base_amount: u64,
     pub bought_amount: u64,
     pub token_price: u64, // lamports
+    pub soft_cap: u64,    // lamports
+    pub hard_cap: u64,    // lamports
+    pub raised_amount: u64,
+    pub liquidity_base_amount: u64,
+    pub liquidity_bps: u16,
+    pub lp_lock_policy: LpLockPolicy,
+    pub proceeds_amount: u64,
+    pub released_amount: u64,
+    pub release_start: i64,
+    pub release_duration: i64,
+    pub milestone_amount: u64,
+    pub milestone_released_amount: u64,
+    pub held_base_amount: u64, // sum of UserPosition::remaining_base, the milestone vote supply
+    pub buyer_vesting: Option<VestingSchedule>,
+    pub creator_vesting: Option<CreatorVesting>,
+    pub creator_vesting_claimed: u64,
+    pub curve: PodCurve,
+    pub start_time: u64,
     pub expire_time: u64,
-    pub is_active: bool
+    pub paused_at: i64,
+    pub total_paused_duration: i64,
+    pub unsold_policy: UnsoldPolicy,
+    pub status: PodStatus,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PodStatus {
+    Scheduled,
+    Live,
+    Paused,
+    SoldOut,
+    Succeeded,
+    Failed,
+    Closed,
+    Graduated,
+}
+
+impl PodStatus {
+    pub fn can_transition_to(&self, next: PodStatus) -> bool {
+        use PodStatus::*;
+        matches!(
+            (*self, next),
+            (Scheduled, Live)
+                | (Scheduled, Closed)
+                | (Live, Paused)
+                | (Live, SoldOut)
+                | (Live, Succeeded)
+                | (Live, Failed)
+                | (Live, Closed)
+                | (Live, Graduated)
+                | (Paused, Live)
+                | (Paused, Succeeded)
+                | (Paused, Failed)
+                | (Paused, Closed)
+                | (SoldOut, Live)
+                | (SoldOut, Succeeded)
+                | (SoldOut, Failed)
+                | (SoldOut, Closed)
+                | (SoldOut, Graduated)
+        )
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsoldPolicy {
+    Burn,
+    ReturnToCreator,
+}
+
+// what happens to the LP tokens minted for graduated liquidity
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LpLockPolicy {
+    Permanent,
+    Timed { duration: i64 },
+}
+
+// cliff plus linear release, measured from when the pod settles
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub cliff: i64,
+    pub duration: i64,
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.cliff >= 0 && self.duration >= self.cliff,
+            MemepodError::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+
+    pub fn vested_amount(&self, total_amount: u64, start: i64, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(start);
+        if elapsed < self.cliff || elapsed < 0 {
+            0
+        } else if self.duration == 0 || elapsed >= self.duration {
+            total_amount
+        } else {
+            ((total_amount as u128) * elapsed as u128 / self.duration as u128) as u64
+        }
+    }
+}
+
+// team tokens held in the pod's vesting vault
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CreatorVesting {
+    pub amount: u64,
+    pub schedule: VestingSchedule,
+}
+
+impl LpLockPolicy {
+    pub fn unlock_time(&self, now: i64) -> i64 {
+        match *self {
+            LpLockPolicy::Permanent => i64::MAX,
+            LpLockPolicy::Timed { duration } => now.saturating_add(duration),
+        }
+    }
 }
 
 impl PodState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
     pub const PREFIX_SEED: &'static [u8] = b"memepod";
+    pub const VESTING_VAULT_SEED: &'static [u8] = b"creator_vesting";
+    pub const QUOTE_VAULT_SEED: &'static [u8] = b"quote_vault";
+
+    // safety_flags bits
+    pub const MINT_AUTHORITY_REVOKED: u8 = 1 << 0;
+    pub const FREEZE_AUTHORITY_REVOKED: u8 = 1 << 1;
+    pub const PROGRAM_CREATED_MINT: u8 = 1 << 2;
+
+    pub fn compute_safety_flags(mint: &Mint) -> u8 {
+        let mut flags = 0;
+        if mint.mint_authority.is_none() {
+            flags |= Self::MINT_AUTHORITY_REVOKED;
+        }
+        if mint.freeze_authority.is_none() {
+            flags |= Self::FREEZE_AUTHORITY_REVOKED;
+        }
+        flags
+    }
+
+    // counts the expiry extension a pending resume would apply, so checks can run before it does
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= 0 && now as u64 >= self.effective_expire_time(now)
+    }
+
+    pub fn effective_expire_time(&self, now: i64) -> u64 {
+        self.expire_time
+            .saturating_add(self.pending_pause_duration(now) as u64)
+    }
+
+    // status once any due start or resume is applied
+    pub fn effective_status(&self, now: i64) -> PodStatus {
+        match self.status {
+            PodStatus::Scheduled if self.is_started(now) => PodStatus::Live,
+            PodStatus::Paused if !self.is_paused(now) => PodStatus::Live,
+            status => status,
+        }
+    }
+
+    pub fn is_started(&self, now: i64) -> bool {
+        now >= 0 && now as u64 >= self.start_time
+    }
+
+    // scheduled pods go live lazily, on the first interaction after start_time
+    pub fn start_if_due(&mut self, now: i64) -> Result<()> {
+        if self.status == PodStatus::Scheduled && self.is_started(now) {
+            self.transition(PodStatus::Live)?;
+        }
+        Ok(())
+    }
+
+    pub fn pause(&mut self, now: i64) -> Result<()> {
+        require!(
+            self.total_paused_duration < MAX_PAUSE_DURATION,
+            MemepodError::PauseLimitReached
+        );
+        self.transition(PodStatus::Paused)?;
+        self.paused_at = now;
+        Ok(())
+    }
+
+    // a pause ends by itself once the pause budget is used up
+    pub fn pause_end(&self) -> i64 {
+        self.paused_at
+            .saturating_add(MAX_PAUSE_DURATION - self.total_paused_duration)
+    }
+
+    pub fn is_paused(&self, now: i64) -> bool {
+        self.status == PodStatus::Paused && now < self.pause_end()
+    }
+
+    // time spent in the current pause, capped at the pause budget
+    fn pending_pause_duration(&self, now: i64) -> i64 {
+        if self.status != PodStatus::Paused {
+            return 0;
+        }
+        now.min(self.pause_end()).saturating_sub(self.paused_at)
+    }
+
+    // resumes the sale, pushing the expiry back by the time spent paused
+    pub fn resume(&mut self, now: i64) -> Result<i64> {
+        let paused_duration = self.pending_pause_duration(now);
+        self.transition(PodStatus::Live)?;
+        self.total_paused_duration += paused_duration;
+        self.expire_time = self.expire_time.saturating_add(paused_duration as u64);
+        Ok(paused_duration)
+    }
+
+    pub fn resume_if_due(&mut self, now: i64) -> Result<()> {
+        if self.status == PodStatus::Paused && !self.is_paused(now) {
+            self.resume(now)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_soft_cap_reached(&self) -> bool {
+        self.raised_amount >= self.soft_cap
+    }
+
+    pub fn is_target_reached(&self) -> bool {
+        self.raised_amount >= self.hard_cap || self.bought_amount == self.base_amount
+    }
+
+    // a pod that sold out with a liquidity reserve is settled by graduation, never by finalize
+    pub fn is_graduation_due(&self) -> bool {
+        self.liquidity_bps > 0
+            && self.liquidity_base_amount > 0
+            && self.is_target_reached()
+            && self.is_soft_cap_reached()
+    }
+
+    pub fn transition(&mut self, next: PodStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            MemepodError::InvalidStatusTransition
+        );
+        self.status = next;
+        Ok(())
+    }
+
+    // sale not settled or closed yet
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            PodStatus::Scheduled | PodStatus::Live | PodStatus::Paused | PodStatus::SoldOut
+        )
+    }
+
+    pub fn is_trading(&self) -> bool {
+        matches!(self.status, PodStatus::Live | PodStatus::SoldOut)
+    }
+
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.status,
+            PodStatus::Succeeded | PodStatus::Failed | PodStatus::Graduated
+        )
+    }
+
+    pub fn is_succeeded(&self) -> bool {
+        matches!(self.status, PodStatus::Succeeded | PodStatus::Graduated)
+    }
+
+    // locks in the creator's proceeds and starts their release schedule
+    pub fn start_release(&mut self, proceeds_amount: u64, now: i64) {
+        // milestone tranches are held back and only released through votes
+        self.milestone_amount = self.milestone_amount.min(proceeds_amount);
+        self.proceeds_amount = proceeds_amount - self.milestone_amount;
+        self.release_start = now;
+    }
+
+    pub fn releasable_amount(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.release_start);
+        let vested = if self.release_duration <= 0 || elapsed >= self.release_duration {
+            self.proceeds_amount
+        } else if elapsed <= 0 {
+            0
+        } else {
+            ((self.proceeds_amount as u128) * elapsed as u128 / self.release_duration as u128)
+                as u64
+        };
+        (vested + self.milestone_released_amount).saturating_sub(self.released_amount)
+    }
+
+    // bought tokens stay in the reserve and are claimed on the vesting schedule
+    pub fn is_claim_mode(&self) -> bool {
+        self.buyer_vesting.is_some()
+    }
+
+    pub fn claimable_amount(&self, user_position: &UserPosition, now: i64) -> u64 {
+        match self.buyer_vesting {
+            Some(schedule) if self.is_succeeded() => schedule
+                .vested_amount(user_position.remaining_base(), self.release_start, now)
+                .saturating_sub(user_position.claimed_amount),
+            _ => 0,
+        }
+    }
+
+    // team tokens vest from settlement like buyer allocations, and go back whole if the pod fails
+    pub fn creator_claimable_amount(&self, now: i64) -> u64 {
+        match self.creator_vesting {
+            Some(vesting) if self.is_succeeded() => vesting
+                .schedule
+                .vested_amount(vesting.amount, self.release_start, now)
+                .saturating_sub(self.creator_vesting_claimed),
+            Some(vesting) if matches!(self.status, PodStatus::Failed | PodStatus::Closed) => {
+                vesting.amount.saturating_sub(self.creator_vesting_claimed)
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn is_refundable(&self, now: i64) -> bool {
+        (self.is_settled() || self.is_expired(now)) && !self.is_soft_cap_reached()
+    }
+
+    pub fn compute_receivable_amount_on_buy(&mut self, quote_amount: u64) -> Result<u64> {
+        let base_amount = self.curve.base_for_quote(
+            self.token_price,
+            self.bought_amount,
+            quote_amount,
+            self.base_amount - self.bought_amount,
+        )?;
+        self.bought_amount += base_amount;
+        Ok(base_amount)
+    }
+
+    pub fn compute_payable_amount_on_buy(&mut self, base_amount: u64) -> Result<u64> {
+        require!(
+            base_amount <= self.base_amount - self.bought_amount,
+            MemepodError::InsufficientFund
+        );
+        let quote_amount =
+            self.curve
+                .quote_for_base(self.token_price, self.bought_amount, base_amount, true)?;
+        self.bought_amount += base_amount;
+        Ok(quote_amount)
+    }
+
+    pub fn compute_payable_amount_on_sell(&mut self, base_amount: u64) -> Result<u64> {
+        self.bought_amount -= base_amount;
+        self.curve
+            .quote_for_base(self.token_price, self.bought_amount, base_amount, false)
+    }
+}
+
+// counts the pods a creator has opened so each round gets its own address
+#[account]
+pub struct CreatorState {
+    pub creator: Pubkey,
+    pub pod_count: u64,
+}
+
+impl CreatorState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"creator";
+
+    pub fn next_index(&mut self, creator: Pubkey) -> u64 {
+        self.creator = creator;
+        let index = self.pod_count;
+        self.pod_count += 1;
+        index
+    }
+}
+
+#[account]
+pub struct UserPosition {
+    pub pod: Pubkey,
+    pub owner: Pubkey,
+    pub quote_paid: u64,
+    pub base_received: u64,
+    pub fees_paid: u64,
+    pub quote_returned: u64,
+    pub base_returned: u64,
+    pub first_buy_time: i64,
+    pub last_buy_time: i64,
+    pub milestone_votes: u8, // bit per milestone index
+    pub milestone_reclaimed: bool,
+    pub claimed_amount: u64,
+}
+
+impl UserPosition {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"position";
+
+    pub fn record_buy(&mut self, quote_amount: u64, base_amount: u64, fee: u64, now: i64) {
+        if self.first_buy_time == 0 {
+            self.first_buy_time = now;
+        }
+        self.last_buy_time = now;
+        self.quote_paid += quote_amount;
+        self.base_received += base_amount;
+        self.fees_paid += fee;
+    }
+
+    pub fn record_return(&mut self, quote_amount: u64, base_amount: u64) {
+        self.quote_returned += quote_amount;
+        self.base_returned += base_amount;
+    }
+
+    pub fn remaining_base(&self) -> u64 {
+        self.base_received.saturating_sub(self.base_returned)
+    }
+
+    // quote still sitting in the pod on behalf of this buyer, fees excluded
+    pub fn remaining_contribution(&self) -> u64 {
+        (self.quote_paid - self.fees_paid).saturating_sub(self.quote_returned)
+    }
+}
+
+pub const MAX_MILESTONES: usize = 8;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MilestoneStatus {
+    #[default]
+    Pending,
+    Released,
+    Rejected,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone, Copy, Default)]
+pub struct Milestone {
+    pub amount: u64,
+    pub description_hash: [u8; 32],
+    pub deadline: i64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub status: MilestoneStatus,
+}
+
+#[account]
+pub struct PodMilestones {
+    pub pod: Pubkey,
+    pub count: u8,
+    pub milestones: [Milestone; MAX_MILESTONES],
+    pub failed: bool,
+    pub reclaim_amount: u64,
+    pub reclaim_weight: u64,
+}
+
+impl PodMilestones {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"milestones";
+
+    // votes only open once the pod has settled, so every deadline must come after expiry
+    pub fn fit_expire_time(&self, expire_time: u64) -> bool {
+        self.milestones[..self.count as usize]
+            .iter()
+            .all(|milestone| milestone.deadline > expire_time as i64)
+    }
+
+    // a position still has a vote to cast or a reclaim share to collect
+    pub fn needs_position(&self, user_position: &UserPosition) -> bool {
+        if user_position.remaining_base() == 0 {
+            return false;
+        }
+        if self.failed {
+            return !user_position.milestone_reclaimed;
+        }
+        self.milestones[..self.count as usize]
+            .iter()
+            .any(|milestone| milestone.status == MilestoneStatus::Pending)
+    }
+}
+
+// pods created before per-creator indexes: seeded without `index` and still on this layout
+#[derive(AnchorDeserialize)]
+pub struct LegacyPodState {
+    pub pod_name: [u8; 32],
+    pub token_name: [u8; 32],
+    pub token_symbol: [u8; 10],
+    pub decimal: u8,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub owner: Pubkey,
+    pub base_amount: u64,
+    pub bought_amount: u64,
+    pub token_price: u64, // lamports
+    pub expire_time: u64,
+    pub is_active: bool,
+}
+
+impl LegacyPodState {
+    // legacy pods share the PodState discriminator, only their layout differs
+    pub fn try_from_account(info: &AccountInfo) -> Result<Self> {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == PodState::DISCRIMINATOR,
+            MemepodError::NotActive
+        );
+        LegacyPodState::deserialize(&mut &data[8..])
+            .map_err(|_| MemepodError::NotActive.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATUSES: [PodStatus; 8] = [
+        PodStatus::Scheduled,
+        PodStatus::Live,
+        PodStatus::Paused,
+        PodStatus::SoldOut,
+        PodStatus::Succeeded,
+        PodStatus::Failed,
+        PodStatus::Closed,
+        PodStatus::Graduated,
+    ];
+
+    #[test]
+    fn open_statuses_can_settle() {
+        use PodStatus::*;
+        for status in [Live, Paused, SoldOut] {
+            assert!(status.can_transition_to(Succeeded), "{:?}", status);
+            assert!(status.can_transition_to(Failed), "{:?}", status);
+            assert!(status.can_transition_to(Closed), "{:?}", status);
+        }
+        assert!(Scheduled.can_transition_to(Live));
+        assert!(Scheduled.can_transition_to(Closed));
+        assert!(Live.can_transition_to(SoldOut));
+        assert!(SoldOut.can_transition_to(Live));
+        assert!(Live.can_transition_to(Graduated));
+        assert!(SoldOut.can_transition_to(Graduated));
+    }
+
+    #[test]
+    fn settled_statuses_are_final() {
+        use PodStatus::*;
+        for from in [Succeeded, Failed, Closed, Graduated] {
+            for to in ALL_STATUSES {
+                assert!(!from.can_transition_to(to), "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+
+    fn empty_pod() -> PodState {
+        let data = vec![0u8; PodState::MAX_SIZE];
+        PodState::deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn creator_vesting_waits_for_settlement() {
+        let mut pod = empty_pod();
+        pod.status = PodStatus::Live;
+        pod.creator_vesting = Some(CreatorVesting {
+            amount: 1_000,
+            schedule: VestingSchedule { cliff: 0, duration: 0 },
+        });
+        assert_eq!(pod.creator_claimable_amount(1_000_000), 0);
+
+        pod.status = PodStatus::Succeeded;
+        pod.release_start = 500;
+        pod.creator_vesting = Some(CreatorVesting {
+            amount: 1_000,
+            schedule: VestingSchedule { cliff: 100, duration: 1_000 },
+        });
+        assert_eq!(pod.creator_claimable_amount(550), 0);
+        assert_eq!(pod.creator_claimable_amount(1_000), 500);
+        pod.creator_vesting_claimed = 200;
+        assert_eq!(pod.creator_claimable_amount(1_000), 300);
+        assert_eq!(pod.creator_claimable_amount(2_000), 800);
+    }
+
+    #[test]
+    fn failed_pods_return_the_creator_allocation() {
+        let mut pod = empty_pod();
+        pod.status = PodStatus::Failed;
+        pod.creator_vesting = Some(CreatorVesting {
+            amount: 1_000,
+            schedule: VestingSchedule { cliff: 100, duration: 1_000 },
+        });
+        pod.creator_vesting_claimed = 250;
+        assert_eq!(pod.creator_claimable_amount(0), 750);
+    }
+
+    #[test]
+    fn no_self_transitions() {
+        for status in ALL_STATUSES {
+            assert!(!status.can_transition_to(status), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn proceeds_release_linearly_from_settlement() {
+        let mut pod = empty_pod();
+        pod.release_duration = 1_000;
+        pod.start_release(10_000, 500);
+        assert_eq!(pod.releasable_amount(400), 0);
+        assert_eq!(pod.releasable_amount(500), 0);
+        assert_eq!(pod.releasable_amount(750), 2_500);
+        pod.released_amount = 2_500;
+        assert_eq!(pod.releasable_amount(1_000), 2_500);
+        assert_eq!(pod.releasable_amount(5_000), 7_500);
+
+        pod.release_duration = 0;
+        pod.released_amount = 0;
+        assert_eq!(pod.releasable_amount(500), 10_000);
+    }
+
+    #[test]
+    fn milestone_tranches_are_held_back_until_released() {
+        let mut pod = empty_pod();
+        pod.milestone_amount = 4_000;
+        pod.start_release(10_000, 0);
+        assert_eq!(pod.proceeds_amount, 6_000);
+        assert_eq!(pod.releasable_amount(1), 6_000);
+        pod.milestone_released_amount = 1_000;
+        assert_eq!(pod.releasable_amount(1), 7_000);
+    }
+
+    #[test]
+    fn vesting_respects_cliff_and_duration() {
+        let schedule = VestingSchedule { cliff: 100, duration: 1_000 };
+        assert_eq!(schedule.vested_amount(1_000, 50, 0), 0);
+        assert_eq!(schedule.vested_amount(1_000, 0, 99), 0);
+        assert_eq!(schedule.vested_amount(1_000, 0, 100), 100);
+        assert_eq!(schedule.vested_amount(1_000, 0, 600), 600);
+        assert_eq!(schedule.vested_amount(1_000, 0, 5_000), 1_000);
+
+        let immediate = VestingSchedule { cliff: 0, duration: 0 };
+        assert_eq!(immediate.vested_amount(1_000, 0, 0), 1_000);
+    }
+
+    #[test]
+    fn buyers_claim_their_vested_remaining_tokens() {
+        let mut pod = empty_pod();
+        pod.buyer_vesting = Some(VestingSchedule { cliff: 0, duration: 1_000 });
+        pod.release_start = 1_000;
+        let data = [0u8; UserPosition::MAX_SIZE];
+        let mut position = UserPosition::deserialize(&mut &data[..]).unwrap();
+        position.base_received = 2_000;
+        position.base_returned = 1_000;
+
+        pod.status = PodStatus::Live;
+        assert_eq!(pod.claimable_amount(&position, 2_000), 0);
+
+        pod.status = PodStatus::Succeeded;
+        assert_eq!(pod.claimable_amount(&position, 1_500), 500);
+        position.claimed_amount = 500;
+        assert_eq!(pod.claimable_amount(&position, 1_500), 0);
+        assert_eq!(pod.claimable_amount(&position, 3_000), 500);
+    }
+
+    #[test]
+    fn sold_out_pods_with_a_liquidity_reserve_must_graduate() {
+        let mut pod = empty_pod();
+        pod.base_amount = 1_000;
+        pod.bought_amount = 1_000;
+        pod.hard_cap = 500;
+        pod.raised_amount = 500;
+        assert!(!pod.is_graduation_due());
+
+        pod.liquidity_bps = 5_000;
+        pod.liquidity_base_amount = 200;
+        assert!(pod.is_graduation_due());
+
+        pod.soft_cap = 600;
+        assert!(!pod.is_graduation_due());
+    }
+
+    #[test]
+    fn legacy_pods_parse_from_the_pre_index_layout() {
+        let owner = Pubkey::new_unique();
+        let mut data = PodState::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0u8; 32 + 32 + 10]);
+        data.push(9);
+        for key in [Pubkey::new_unique(), Pubkey::new_unique(), owner] {
+            data.extend_from_slice(key.as_ref());
+        }
+        for value in [1_000u64, 400, 30, 99] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(1);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key, false, true, &mut lamports, &mut data, &crate::ID, false, 0,
+        );
+        let legacy = LegacyPodState::try_from_account(&info).unwrap();
+        assert_eq!(legacy.owner, owner);
+        assert_eq!(legacy.base_amount - legacy.bought_amount, 600);
+        assert!(legacy.is_active);
+    }
+
+    #[test]
+    fn positions_stay_open_for_pending_milestones() {
+        let mut milestones = PodMilestones {
+            pod: Pubkey::default(),
+            count: 2,
+            milestones: [Milestone::default(); MAX_MILESTONES],
+            failed: false,
+            reclaim_amount: 0,
+            reclaim_weight: 0,
+        };
+        let data = [0u8; UserPosition::MAX_SIZE];
+        let mut position = UserPosition::deserialize(&mut &data[..]).unwrap();
+        assert!(!milestones.needs_position(&position));
+
+        position.base_received = 100;
+        assert!(milestones.needs_position(&position));
+        milestones.milestones[0].status = MilestoneStatus::Released;
+        milestones.milestones[1].status = MilestoneStatus::Released;
+        assert!(!milestones.needs_position(&position));
+
+        milestones.failed = true;
+        assert!(milestones.needs_position(&position));
+        position.milestone_reclaimed = true;
+        assert!(!milestones.needs_position(&position));
+    }
+
+    #[test]
+    fn expiry_must_stay_before_milestone_deadlines() {
+        let mut milestones = PodMilestones {
+            pod: Pubkey::default(),
+            count: 1,
+            milestones: [Milestone::default(); MAX_MILESTONES],
+            failed: false,
+            reclaim_amount: 0,
+            reclaim_weight: 0,
+        };
+        milestones.milestones[0].deadline = 1_000;
+        assert!(milestones.fit_expire_time(999));
+        assert!(!milestones.fit_expire_time(1_000));
+    }
+
+    #[test]
+    fn lapsed_pauses_extend_expiry_before_resume() {
+        let mut pod = empty_pod();
+        pod.expire_time = 1_000;
+        pod.status = PodStatus::Paused;
+        pod.paused_at = 900;
+        pod.total_paused_duration = MAX_PAUSE_DURATION - 200;
+
+        // still paused, expiry moves with the clock
+        assert_eq!(pod.effective_status(1_000), PodStatus::Paused);
+        assert!(!pod.is_expired(1_000));
+
+        // pause budget ran out at 1_100, the window is open until 1_200
+        assert_eq!(pod.effective_status(1_150), PodStatus::Live);
+        assert_eq!(pod.effective_expire_time(1_150), 1_200);
+        assert!(!pod.is_expired(1_150));
+        assert!(pod.is_expired(1_200));
 
-    pub fn compute_receivable_amount_on_buy(&mut self, quote_amount: u64) -> u64 {
-        let base_amount = (quote_amount as u128)
-            .checked_mul(self.token_price as u128)
-            .unwrap()
-            .checked_div(1000000000)
-            .unwrap();
-        self.bought_amount += base_amount as u64;
-        base_amount as u64
+        pod.resume_if_due(1_150).unwrap();
+        assert_eq!(pod.status, PodStatus::Live);
+        assert_eq!(pod.expire_time, 1_200);
+        assert!(!pod.is_expired(1_150));
     }
 }
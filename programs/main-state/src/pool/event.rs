@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct GraduateEvent {
+    pub pod: Pubkey,
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub lp_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapEvent {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub base_to_quote: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddLiquidityEvent {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub lp_amount: u64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RemoveLiquidityEvent {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub lp_amount: u64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityLockedEvent {
+    pub pod: Pubkey,
+    pub pool: Pubkey,
+    pub lock: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub unlock_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityUnlockedEvent {
+    pub pod: Pubkey,
+    pub pool: Pubkey,
+    pub beneficiary: Pubkey,
+    pub lp_amount: u64,
+    pub timestamp: i64,
+}
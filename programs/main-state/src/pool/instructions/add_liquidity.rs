@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
+};
+use crate::{
+    error::MemepodError,
+    utils::{amount_after_transfer_fee, sync_native_amount, unwrap_native_account},
+    AddLiquidityEvent, MainState, PoolState,
+};
+
+pub fn add_liquidity(
+    ctx: Context<AAddLiquidity>,
+    lp_amount: u64,
+    max_base_in: u64,
+    max_quote_in: u64,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_POOL)?;
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user = ctx.accounts.user.to_account_info();
+    let user_quote_ata = &ctx.accounts.user_quote_ata;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let (base_amount, quote_amount) = pool_state.compute_share(lp_amount, true)?;
+    require!(
+        base_amount <= max_base_in && quote_amount <= max_quote_in,
+        MemepodError::SlippageExceeded
+    );
+    // reserves only grow by what lands in the vault after transfer fees, and lp is
+    // minted on that same amount so the depositor bears the fee
+    let base_received = amount_after_transfer_fee(&ctx.accounts.base_mint, base_amount)?;
+    let lp_amount = pool_state.lp_for_base(base_received, lp_amount)?;
+    pool_state.base_reserve += base_received;
+    pool_state.quote_reserve += quote_amount;
+    pool_state.lp_supply += lp_amount;
+
+    sync_native_amount(
+        user.clone(),
+        user_quote_ata,
+        quote_amount,
+        system_program.clone(),
+        token_program.clone(),
+    )?;
+
+    let base_transfer_cpi_account = TransferChecked {
+        from: ctx.accounts.user_base_ata.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        to: ctx.accounts.pool_base_vault.to_account_info(),
+        authority: user.clone(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.base_token_program.to_account_info(),
+            base_transfer_cpi_account,
+        ),
+        base_amount,
+        ctx.accounts.base_mint.decimals,
+    )?;
+
+    let quote_transfer_cpi_account = Transfer {
+        from: user_quote_ata.to_account_info(),
+        to: ctx.accounts.pool_quote_vault.to_account_info(),
+        authority: user.clone(),
+    };
+    token::transfer(
+        CpiContext::new(token_program.clone(), quote_transfer_cpi_account),
+        quote_amount,
+    )?;
+
+    let lp_mint_cpi_accounts = MintTo {
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        to: ctx.accounts.user_lp_ata.to_account_info(),
+        authority: pool_state.to_account_info(),
+    };
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            lp_mint_cpi_accounts,
+            &[&[
+                PoolState::PREFIX_SEED,
+                pool_state.pod.as_ref(),
+                &[ctx.bumps.pool_state],
+            ]],
+        ),
+        lp_amount,
+    )?;
+
+    // unwrap sol
+    unwrap_native_account(
+        user.clone(),
+        user_quote_ata,
+        token_program,
+    )?;
+
+    emit!(AddLiquidityEvent {
+        user: user.key(),
+        pool: pool_state.key(),
+        lp_amount,
+        base_amount,
+        quote_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AAddLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, pool_state.pod.as_ref()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(address = pool_state.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = pool_state.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pool_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub pool_base_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = pool_state,
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = user,
+        associated_token::token_program = base_token_program,
+    )]
+    pub user_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = quote_mint,
+        associated_token::authority = user,
+    )]
+    pub user_quote_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+    )]
+    pub user_lp_ata: Box<Account<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
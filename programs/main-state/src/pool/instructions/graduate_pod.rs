@@ -0,0 +1,291 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, SyncNative, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
+};
+use crate::{
+    constants::BPS_DIV,
+    error::MemepodError,
+    utils::{amount_after_transfer_fee, sqrt_u128, transfer_lamports},
+    GraduateEvent, LiquidityLock, LiquidityLockedEvent, MainState, PodState, PodStatus,
+    PoolState,
+};
+
+pub fn graduate_pod(ctx: Context<AGraduatePod>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_FINALIZE)?;
+
+    let pod_state = &mut ctx.accounts.pod_state;
+    require!(pod_state.is_trading(), MemepodError::NotActive);
+    require!(
+        pod_state.liquidity_base_amount > 0 && pod_state.liquidity_bps > 0,
+        MemepodError::GraduationDisabled
+    );
+    require!(
+        pod_state.is_target_reached() && pod_state.is_soft_cap_reached(),
+        MemepodError::TargetNotReached
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let base_amount = pod_state.liquidity_base_amount;
+    let quote_amount = (pod_state.raised_amount as u128)
+        .checked_mul(pod_state.liquidity_bps.into())
+        .unwrap()
+        .checked_div(BPS_DIV)
+        .unwrap() as u64;
+    require!(quote_amount > 0, MemepodError::InsufficientFund);
+
+    pod_state.transition(PodStatus::Graduated)?;
+    pod_state.liquidity_base_amount = 0;
+    let proceeds_amount = pod_state.raised_amount - quote_amount;
+    pod_state.start_release(proceeds_amount, now);
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let pod_signer_seeds: &[&[u8]] = &[
+        PodState::PREFIX_SEED,
+        pod_state.base_mint.as_ref(),
+        pod_state.quote_mint.as_ref(),
+        pod_state.owner.as_ref(),
+        &pod_state.index.to_le_bytes(),
+        &[ctx.bumps.pod_state],
+    ];
+
+    // seeding pool with reserved tokens (meme)
+    let base_transfer_cpi_accounts = TransferChecked {
+        from: ctx.accounts.reserver_base_ata.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        to: ctx.accounts.pool_base_vault.to_account_info(),
+        authority: pod_state.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.base_token_program.to_account_info(),
+            base_transfer_cpi_accounts,
+            &[pod_signer_seeds],
+        ),
+        base_amount,
+        ctx.accounts.base_mint.decimals,
+    )?;
+    // the pool is seeded with what lands in the vault after transfer fees
+    let base_amount = amount_after_transfer_fee(&ctx.accounts.base_mint, base_amount)?;
+
+    if pod_state.native_quote {
+        // native pods wrap their share straight into the pool's wsol vault
+        let quote_vault = ctx
+            .accounts
+            .quote_vault
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+        let pod_key = pod_state.key();
+        transfer_lamports(
+            quote_vault.to_account_info(),
+            ctx.accounts.pool_quote_vault.to_account_info(),
+            quote_amount,
+            ctx.accounts.system_program.to_account_info(),
+            &[&[
+                PodState::QUOTE_VAULT_SEED,
+                pod_key.as_ref(),
+                &[ctx.bumps.quote_vault],
+            ]],
+        )?;
+        let sync_accounts = SyncNative {
+            account: ctx.accounts.pool_quote_vault.to_account_info(),
+        };
+        token::sync_native(CpiContext::new(token_program.clone(), sync_accounts))?;
+    } else {
+        let reserver_quote_ata = ctx
+            .accounts
+            .reserver_quote_ata
+            .as_ref()
+            .ok_or(MemepodError::MissingQuoteAccount)?;
+
+        // seeding pool with share of raised amount (sol)
+        let quote_transfer_cpi_accounts = Transfer {
+            from: reserver_quote_ata.to_account_info(),
+            to: ctx.accounts.pool_quote_vault.to_account_info(),
+            authority: pod_state.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                quote_transfer_cpi_accounts,
+                &[pod_signer_seeds],
+            ),
+            quote_amount,
+        )?;
+    }
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let lp_amount = sqrt_u128(base_amount as u128 * quote_amount as u128) as u64;
+    pool_state.pod = pod_state.key();
+    pool_state.base_mint = pod_state.base_mint;
+    pool_state.quote_mint = pod_state.quote_mint;
+    pool_state.lp_mint = ctx.accounts.lp_mint.key();
+    pool_state.base_reserve = base_amount;
+    pool_state.quote_reserve = quote_amount;
+    pool_state.lp_supply = lp_amount;
+    pool_state.created_at = now;
+
+    let pod_key = pod_state.key();
+    let unlock_time = pod_state.lp_lock_policy.unlock_time(now);
+    let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+    liquidity_lock.pool = pool_state.key();
+    liquidity_lock.pod = pod_key;
+    liquidity_lock.beneficiary = pod_state.owner;
+    liquidity_lock.lp_amount = lp_amount;
+    liquidity_lock.locked_at = now;
+    liquidity_lock.unlock_time = unlock_time;
+
+    // seeded liquidity is minted straight into the lock
+    let lp_mint_cpi_accounts = MintTo {
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        to: ctx.accounts.lock_lp_ata.to_account_info(),
+        authority: pool_state.to_account_info(),
+    };
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program,
+            lp_mint_cpi_accounts,
+            &[&[
+                PoolState::PREFIX_SEED,
+                pod_key.as_ref(),
+                &[ctx.bumps.pool_state],
+            ]],
+        ),
+        lp_amount,
+    )?;
+
+    emit!(GraduateEvent {
+        pod: pod_key,
+        pool: pool_state.key(),
+        base_mint: pool_state.base_mint,
+        base_amount,
+        quote_amount,
+        lp_amount,
+        timestamp: now,
+    });
+
+    emit!(LiquidityLockedEvent {
+        pod: pod_key,
+        pool: pool_state.key(),
+        lock: liquidity_lock.key(),
+        lp_mint: pool_state.lp_mint,
+        lp_amount,
+        unlock_time,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AGraduatePod<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub creator: SystemAccount<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PodState::PREFIX_SEED,
+            base_mint.key().as_ref(),
+            quote_mint.key().as_ref(),
+            creator.key().as_ref(),
+            pod_state.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pod_state: Box<Account<'info, PodState>>,
+
+    #[account(address = pod_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(address = pod_state.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pod_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub reserver_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = pod_state,
+    )]
+    pub reserver_quote_ata: Option<Box<Account<'info, TokenAccount>>>,
+    // lamport vault, passed instead of the quote token account for native-quote pods
+    #[account(
+        mut,
+        seeds = [PodState::QUOTE_VAULT_SEED, pod_state.key().as_ref()],
+        bump,
+    )]
+    pub quote_vault: Option<SystemAccount<'info>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        seeds = [PoolState::PREFIX_SEED, pod_state.key().as_ref()],
+        bump,
+        space = 8 + PoolState::MAX_SIZE
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        seeds = [PoolState::LP_MINT_SEED, pool_state.key().as_ref()],
+        bump,
+        mint::decimals = PoolState::LP_DECIMALS,
+        mint::authority = pool_state,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        associated_token::mint = base_mint,
+        associated_token::authority = pool_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub pool_base_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init,
+        payer = cranker,
+        associated_token::mint = quote_mint,
+        associated_token::authority = pool_state,
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        seeds = [LiquidityLock::PREFIX_SEED, pool_state.key().as_ref()],
+        bump,
+        space = 8 + LiquidityLock::MAX_SIZE
+    )]
+    pub liquidity_lock: Box<Account<'info, LiquidityLock>>,
+    #[account(
+        init,
+        payer = cranker,
+        associated_token::mint = lp_mint,
+        associated_token::authority = liquidity_lock,
+    )]
+    pub lock_lp_ata: Box<Account<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,14 @@
+pub mod graduate_pod;
+pub use graduate_pod::*;
+
+pub mod swap;
+pub use swap::*;
+
+pub mod add_liquidity;
+pub use add_liquidity::*;
+
+pub mod remove_liquidity;
+pub use remove_liquidity::*;
+
+pub mod unlock_liquidity;
+pub use unlock_liquidity::*;
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, TokenInterface, TransferChecked},
+};
+use crate::{
+    error::MemepodError,
+    utils::{
+        amount_after_transfer_fee, calculate_trading_fee, sync_native_amount,
+        unwrap_native_account,
+    },
+    MainState, PoolState, QuoteMintConfig, SwapEvent,
+};
+
+pub fn swap(
+    ctx: Context<ASwap>,
+    amount_in: u64,
+    min_amount_out: u64,
+    base_to_quote: bool,
+) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_POOL)?;
+    let fees = main_state.fee_settings(
+        &ctx.accounts.pool_state.quote_mint,
+        ctx.accounts.quote_config.as_deref().map(|config| &**config),
+    )?;
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user = ctx.accounts.user.to_account_info();
+    let user_base_ata = &ctx.accounts.user_base_ata;
+    let user_quote_ata = &ctx.accounts.user_quote_ata;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // transfer fees withheld on incoming base never reach the pool
+    let received_in = if base_to_quote {
+        amount_after_transfer_fee(&ctx.accounts.base_mint, amount_in)?
+    } else {
+        amount_in
+    };
+    // fee stays in the pool for liquidity providers
+    let fee = calculate_trading_fee(fees.trading_fee, received_in);
+    let amount_out = pool_state.compute_swap_output(received_in - fee, base_to_quote)?;
+    require!(
+        amount_out > 0 && amount_out >= min_amount_out,
+        MemepodError::SlippageExceeded
+    );
+    pool_state.apply_swap(received_in, amount_out, base_to_quote);
+
+    let base_mint = &ctx.accounts.base_mint;
+    let base_token_program = ctx.accounts.base_token_program.to_account_info();
+    let pool_signer_seeds: &[&[u8]] = &[
+        PoolState::PREFIX_SEED,
+        pool_state.pod.as_ref(),
+        &[ctx.bumps.pool_state],
+    ];
+
+    if base_to_quote {
+        let input_transfer_cpi_account = TransferChecked {
+            from: user_base_ata.to_account_info(),
+            mint: base_mint.to_account_info(),
+            to: ctx.accounts.pool_base_vault.to_account_info(),
+            authority: user.clone(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new(base_token_program, input_transfer_cpi_account),
+            amount_in,
+            base_mint.decimals,
+        )?;
+
+        let output_transfer_cpi_account = Transfer {
+            from: ctx.accounts.pool_quote_vault.to_account_info(),
+            to: user_quote_ata.to_account_info(),
+            authority: pool_state.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                output_transfer_cpi_account,
+                &[pool_signer_seeds],
+            ),
+            amount_out,
+        )?;
+    } else {
+        sync_native_amount(
+            user.clone(),
+            user_quote_ata,
+            amount_in,
+            system_program.clone(),
+            token_program.clone(),
+        )?;
+
+        let input_transfer_cpi_account = Transfer {
+            from: user_quote_ata.to_account_info(),
+            to: ctx.accounts.pool_quote_vault.to_account_info(),
+            authority: user.clone(),
+        };
+        token::transfer(
+            CpiContext::new(token_program.clone(), input_transfer_cpi_account),
+            amount_in,
+        )?;
+
+        let output_transfer_cpi_account = TransferChecked {
+            from: ctx.accounts.pool_base_vault.to_account_info(),
+            mint: base_mint.to_account_info(),
+            to: user_base_ata.to_account_info(),
+            authority: pool_state.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                base_token_program,
+                output_transfer_cpi_account,
+                &[pool_signer_seeds],
+            ),
+            amount_out,
+            base_mint.decimals,
+        )?;
+    }
+
+    // unwrap sol
+    unwrap_native_account(
+        user.clone(),
+        user_quote_ata,
+        token_program,
+    )?;
+
+    emit!(SwapEvent {
+        user: user.key(),
+        pool: pool_state.key(),
+        base_to_quote,
+        amount_in,
+        amount_out,
+        fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASwap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        seeds = [PoolState::PREFIX_SEED, pool_state.pod.as_ref()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(address = pool_state.base_mint)]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(address = pool_state.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [QuoteMintConfig::PREFIX_SEED, quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub quote_config: Option<Box<Account<'info, QuoteMintConfig>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = pool_state,
+        associated_token::token_program = base_token_program,
+    )]
+    pub pool_base_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = pool_state,
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = base_mint,
+        associated_token::authority = user,
+        associated_token::token_program = base_token_program,
+    )]
+    pub user_base_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = quote_mint,
+        associated_token::authority = user,
+    )]
+    pub user_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+use crate::{error::MemepodError, LiquidityLock, LiquidityUnlockedEvent, MainState, PoolState};
+
+pub fn unlock_liquidity(ctx: Context<AUnlockLiquidity>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        main_state.initialized.eq(&true),
+        MemepodError::Uninitialized
+    );
+    main_state.require_not_paused(MainState::PAUSE_POOL)?;
+
+    let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= liquidity_lock.unlock_time,
+        MemepodError::LiquidityLocked
+    );
+    require!(liquidity_lock.lp_amount > 0, MemepodError::InsufficientFund);
+
+    let lp_amount = liquidity_lock.lp_amount;
+    liquidity_lock.lp_amount = 0;
+
+    let lp_transfer_cpi_account = Transfer {
+        from: ctx.accounts.lock_lp_ata.to_account_info(),
+        to: ctx.accounts.beneficiary_lp_ata.to_account_info(),
+        authority: liquidity_lock.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            lp_transfer_cpi_account,
+            &[&[
+                LiquidityLock::PREFIX_SEED,
+                liquidity_lock.pool.as_ref(),
+                &[ctx.bumps.liquidity_lock],
+            ]],
+        ),
+        lp_amount,
+    )?;
+
+    emit!(LiquidityUnlockedEvent {
+        pod: liquidity_lock.pod,
+        pool: liquidity_lock.pool,
+        beneficiary: liquidity_lock.beneficiary,
+        lp_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AUnlockLiquidity<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    #[account(
+        seeds = [MainState::PREFIX_SEED],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [PoolState::PREFIX_SEED, pool_state.pod.as_ref()],
+        bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+    #[account(address = pool_state.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityLock::PREFIX_SEED, pool_state.key().as_ref()],
+        bump,
+        has_one = beneficiary @ MemepodError::Unauthorised,
+    )]
+    pub liquidity_lock: Box<Account<'info, LiquidityLock>>,
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = liquidity_lock,
+    )]
+    pub lock_lp_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = lp_mint,
+        associated_token::authority = beneficiary,
+    )]
+    pub beneficiary_lp_ata: Box<Account<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
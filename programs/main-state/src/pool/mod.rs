@@ -0,0 +1,8 @@
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
+
+pub mod event;
+pub use event::*;
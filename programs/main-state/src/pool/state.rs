@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MemepodError;
+
+#[account]
+pub struct PoolState {
+    pub pod: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+    pub lp_supply: u64,
+    pub created_at: i64,
+}
+
+impl PoolState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"pool";
+    pub const LP_MINT_SEED: &'static [u8] = b"lp_mint";
+    pub const LP_DECIMALS: u8 = 9;
+
+    // x * y = k, the fee having already been taken out of `amount_in`
+    pub fn compute_swap_output(&self, amount_in: u64, base_to_quote: bool) -> Result<u64> {
+        let (reserve_in, reserve_out) = if base_to_quote {
+            (self.base_reserve, self.quote_reserve)
+        } else {
+            (self.quote_reserve, self.base_reserve)
+        };
+        let amount_out = (amount_in as u128)
+            .checked_mul(reserve_out as u128)
+            .ok_or(MemepodError::MathOverflow)?
+            .checked_div(reserve_in as u128 + amount_in as u128)
+            .ok_or(MemepodError::MathOverflow)?;
+        Ok(amount_out as u64)
+    }
+
+    pub fn apply_swap(&mut self, amount_in: u64, amount_out: u64, base_to_quote: bool) {
+        if base_to_quote {
+            self.base_reserve += amount_in;
+            self.quote_reserve -= amount_out;
+        } else {
+            self.quote_reserve += amount_in;
+            self.base_reserve -= amount_out;
+        }
+    }
+
+    // reserves backing `lp_amount`, rounded up on deposit and down on withdrawal
+    pub fn compute_share(&self, lp_amount: u64, round_up: bool) -> Result<(u64, u64)> {
+        require!(self.lp_supply > 0, MemepodError::InsufficientFund);
+        let share = |reserve: u64| -> Result<u64> {
+            let num = (lp_amount as u128)
+                .checked_mul(reserve as u128)
+                .ok_or(MemepodError::MathOverflow)?;
+            let den = self.lp_supply as u128;
+            let mut amount = num / den;
+            if round_up && num % den != 0 {
+                amount += 1;
+            }
+            u64::try_from(amount).map_err(|_| MemepodError::MathOverflow.into())
+        };
+        Ok((share(self.base_reserve)?, share(self.quote_reserve)?))
+    }
+
+    // lp backed by `base_amount` landing in the base vault, capped at `lp_amount`
+    pub fn lp_for_base(&self, base_amount: u64, lp_amount: u64) -> Result<u64> {
+        if self.base_reserve == 0 {
+            return Ok(lp_amount);
+        }
+        let lp = (base_amount as u128)
+            .checked_mul(self.lp_supply as u128)
+            .ok_or(MemepodError::MathOverflow)?
+            / self.base_reserve as u128;
+        Ok(lp.min(lp_amount as u128) as u64)
+    }
+}
+
+#[account]
+pub struct LiquidityLock {
+    pub pool: Pubkey,
+    pub pod: Pubkey,
+    pub beneficiary: Pubkey,
+    pub lp_amount: u64,
+    pub locked_at: i64,
+    pub unlock_time: i64,
+}
+
+impl LiquidityLock {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const PREFIX_SEED: &'static [u8] = b"lp_lock";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(base_reserve: u64, quote_reserve: u64, lp_supply: u64) -> PoolState {
+        PoolState {
+            pod: Pubkey::default(),
+            base_mint: Pubkey::default(),
+            quote_mint: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+            base_reserve,
+            quote_reserve,
+            lp_supply,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn swaps_keep_the_constant_product() {
+        let mut pool_state = pool(1_000_000, 2_000_000, 0);
+        let k = 1_000_000u128 * 2_000_000;
+
+        // 1_000_000 * 1_000_000 / 3_000_000 = 333_333.33, rounded against the trader
+        let base_out = pool_state.compute_swap_output(1_000_000, false).unwrap();
+        assert_eq!(base_out, 333_333);
+        pool_state.apply_swap(1_000_000, base_out, false);
+        assert_eq!(pool_state.quote_reserve, 3_000_000);
+        assert_eq!(pool_state.base_reserve, 666_667);
+        assert!(pool_state.base_reserve as u128 * pool_state.quote_reserve as u128 >= k);
+
+        // 1_000 * 3_000_000 / 667_667 = 4_493.26
+        let quote_out = pool_state.compute_swap_output(1_000, true).unwrap();
+        assert_eq!(quote_out, 4_493);
+        pool_state.apply_swap(1_000, quote_out, true);
+        assert!(pool_state.base_reserve as u128 * pool_state.quote_reserve as u128 >= k);
+    }
+
+    #[test]
+    fn deposits_round_up_and_withdrawals_round_down() {
+        let pool_state = pool(1_000, 3_001, 300);
+        // 7 / 300 of the reserves: 23.33 base and 70.02 quote
+        assert_eq!(pool_state.compute_share(7, true).unwrap(), (24, 71));
+        assert_eq!(pool_state.compute_share(7, false).unwrap(), (23, 70));
+        // exact shares are not rounded either way
+        assert_eq!(pool_state.compute_share(30, true).unwrap(), (100, 301));
+        assert_eq!(pool_state.compute_share(30, false).unwrap(), (100, 300));
+
+        assert!(pool(1_000, 3_001, 0).compute_share(7, false).is_err());
+    }
+
+    #[test]
+    fn lp_follows_the_base_left_after_transfer_fees() {
+        let pool_state = pool(1_000, 3_000, 300);
+        // 30 lp asks for 100 base, a 1% fee lands 99: 99 * 300 / 1_000 = 29.7
+        assert_eq!(pool_state.lp_for_base(99, 30).unwrap(), 29);
+        assert_eq!(pool_state.lp_for_base(100, 30).unwrap(), 30);
+        // never more than requested
+        assert_eq!(pool_state.lp_for_base(200, 30).unwrap(), 30);
+        // an empty base vault leaves nothing to measure against
+        assert_eq!(pool(0, 3_000, 300).lp_for_base(99, 30).unwrap(), 30);
+    }
+}
@@ -2,8 +2,48 @@ use crate::{
     constants::{FEE_PER_DIV, NATIVE_MINT_STR},
     error::MemepodError,
 };
-use anchor_lang::{prelude::*, solana_program::program::invoke};
-use anchor_spl::token::{self, CloseAccount, SyncNative, TokenAccount};
+use anchor_lang::{prelude::*, solana_program::program::invoke, system_program};
+use anchor_spl::{
+    token::{self, CloseAccount, SyncNative, TokenAccount},
+    token_2022::{
+        self,
+        spl_token_2022::{
+            extension::{
+                transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+            },
+            state::Mint,
+        },
+    },
+    token_interface,
+};
+
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    mint.to_string() == NATIVE_MINT_STR
+}
+
+// amount that lands in the destination once a token-2022 transfer fee is withheld
+pub fn amount_after_transfer_fee(
+    mint: &InterfaceAccount<token_interface::Mint>,
+    amount: u64,
+) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != token_2022::ID {
+        return Ok(amount);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    amount_after_epoch_fee(&mint_data, Clock::get()?.epoch, amount)
+}
+
+fn amount_after_epoch_fee(mint_data: &[u8], epoch: u64, amount: u64) -> Result<u64> {
+    let mint_state = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or(MemepodError::MathOverflow)?,
+        Err(_) => 0,
+    };
+    Ok(amount - fee)
+}
 
 pub fn check_balance_on_pod_creator(ata: &TokenAccount, require_amount: u64) -> bool {
     if (ata.mint.to_string() == NATIVE_MINT_STR) {
@@ -25,6 +65,46 @@ pub fn calculate_trading_fee(fee: u16, amount: u64) -> u64 {
         .unwrap() as u64
 }
 
+// smallest amount that still leaves `net_amount` once the trading fee is taken
+pub fn calculate_amount_with_trading_fee(fee: u16, net_amount: u64) -> u64 {
+    let fee_div = FEE_PER_DIV * 100;
+    let numerator = (net_amount as u128).checked_mul(fee_div).unwrap();
+    let denominator = fee_div.checked_sub(fee.into()).unwrap();
+    let amount = numerator / denominator;
+    if amount * denominator < numerator {
+        return (amount + 1) as u64;
+    }
+    amount as u64
+}
+
+// splits a trading fee between the protocol owner and the pod creator,
+// weighted by `MainState::owner_fee` and `MainState::creator_fee`
+pub fn split_trading_fee(fee: u64, owner_fee: u16, creator_fee: u16) -> (u64, u64) {
+    let total_share = owner_fee as u128 + creator_fee as u128;
+    if total_share == 0 {
+        return (fee, 0);
+    }
+    let owner_amount = (fee as u128)
+        .checked_mul(owner_fee.into())
+        .unwrap()
+        .checked_div(total_share)
+        .unwrap() as u64;
+    (owner_amount, fee - owner_amount)
+}
+
+pub fn sqrt_u128(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
 pub fn close_token_account<'a>(
     owner: AccountInfo<'a>,
     ata: AccountInfo<'a>,
@@ -39,6 +119,36 @@ pub fn close_token_account<'a>(
     Ok(())
 }
 
+// unwraps sol, spl quote accounts are left open
+pub fn unwrap_native_account<'a>(
+    owner: AccountInfo<'a>,
+    ata: &Account<'a, TokenAccount>,
+    token_program: AccountInfo<'a>,
+) -> Result<()> {
+    if !is_native_mint(&ata.mint) {
+        return Ok(());
+    }
+    close_token_account(owner, ata.to_account_info(), token_program)
+}
+
+// plain lamport transfer, signed when `from` is a pda such as the quote vault
+pub fn transfer_lamports<'a>(
+    from: AccountInfo<'a>,
+    to: AccountInfo<'a>,
+    amount: u64,
+    system_program: AccountInfo<'a>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let cpi_accounts = system_program::Transfer { from, to };
+    system_program::transfer(
+        CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds),
+        amount,
+    )
+}
+
 pub fn sync_native_amount<'a>(
     owner: AccountInfo<'a>,
     ata: &Account<'a, TokenAccount>,
@@ -46,13 +156,17 @@ pub fn sync_native_amount<'a>(
     system_program: AccountInfo<'a>,
     token_program: AccountInfo<'a>,
 ) -> Result<()> {
+    // spl quote tokens are spent from the existing balance
+    if !is_native_mint(&ata.mint) {
+        return Ok(());
+    }
     let ata_balance = ata.amount;
     let mut sync_amount = 0;
     if require_amount > ata_balance {
         sync_amount = require_amount - ata_balance
     }
     if sync_amount != 0 {
-        if (owner.lamports.borrow().clone() < require_amount) {
+        if owner.lamports() < require_amount {
             return Err(MemepodError::InsufficientFund.into());
         }
         let sol_transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
@@ -75,3 +189,68 @@ pub fn sync_native_amount<'a>(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        transfer_fee::TransferFee, ExtensionType, StateWithExtensionsMut,
+    };
+
+    fn mint_data(fee: Option<(u64, u16, u64)>) -> Vec<u8> {
+        let extensions: &[ExtensionType] = if fee.is_some() {
+            &[ExtensionType::TransferFeeConfig]
+        } else {
+            &[]
+        };
+        let len = ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        if let Some((epoch, basis_points, maximum_fee)) = fee {
+            let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.newer_transfer_fee = TransferFee {
+                epoch: epoch.into(),
+                maximum_fee: maximum_fee.into(),
+                transfer_fee_basis_points: basis_points.into(),
+            };
+        }
+        state.base.decimals = 9;
+        state.base.is_initialized = true;
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn transfer_fee_is_withheld_from_the_amount() {
+        // 1% capped at 5_000
+        let data = mint_data(Some((0, 100, 5_000)));
+        assert_eq!(amount_after_epoch_fee(&data, 0, 100_000).unwrap(), 99_000);
+        assert_eq!(amount_after_epoch_fee(&data, 0, 1_000_000).unwrap(), 995_000);
+        assert_eq!(amount_after_epoch_fee(&data, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn transfer_fee_applies_from_its_epoch() {
+        let data = mint_data(Some((10, 100, u64::MAX)));
+        assert_eq!(amount_after_epoch_fee(&data, 9, 100_000).unwrap(), 100_000);
+        assert_eq!(amount_after_epoch_fee(&data, 10, 100_000).unwrap(), 99_000);
+    }
+
+    #[test]
+    fn mints_without_a_transfer_fee_keep_the_amount() {
+        let data = mint_data(None);
+        assert_eq!(amount_after_epoch_fee(&data, 0, 123_456).unwrap(), 123_456);
+    }
+
+    #[test]
+    fn trading_fee_splits_by_share() {
+        assert_eq!(split_trading_fee(1_000, 3, 1), (750, 250));
+        // rounding dust goes to the creator so nothing is lost
+        assert_eq!(split_trading_fee(1_001, 1, 2), (333, 668));
+        assert_eq!(split_trading_fee(1_000, 1, 0), (1_000, 0));
+        assert_eq!(split_trading_fee(1_000, 0, 1), (0, 1_000));
+        // no shares configured keeps the whole fee with the protocol
+        assert_eq!(split_trading_fee(1_000, 0, 0), (1_000, 0));
+    }
+}